@@ -1,13 +1,3 @@
-#![feature(
-    nll,
-    never_type,
-    specialization,
-    map_entry_replace,
-    uniform_paths,
-    drain_filter,
-    try_from,
-    try_trait
-)]
 #![warn(clippy::all, clippy::nursery, clippy::pedantic)]
 #![allow(
     clippy::expl_impl_clone_on_copy,
@@ -17,23 +7,64 @@
 )]
 
 // mod y2016;
-// mod y2017;
+#[cfg(test)]
+mod regression;
 mod util;
+mod y2017;
 mod y2018;
 
-use std::{env, io, process};
+use std::{env, error, fmt::Debug, io::Read, path::PathBuf, process, str::FromStr, time::Duration};
 
-use util::{Answer, Error};
+use util::{
+    bench, input_path,
+    trace::{Frames, Terminal},
+    Error, ExitCode, Key, Registry, Solver, Source,
+};
 
 macro_rules! show_usage {
     ($a:expr, $($arg:tt)+) => ({
         eprintln!($($arg)+);
         eprintln!();
-        eprintln!("USAGE:\n\t{} <year> <question> <part>", $a.bin_name);
-        process::exit(2)
+        eprintln!("USAGE:");
+        eprintln!("\t{} <year> <question> <part> [--input <file> | --test[=N]] [--trace[=<dir>]]", $a.bin_name);
+        eprintln!("\t{} list [year]", $a.bin_name);
+        eprintln!("\t{} all [year]", $a.bin_name);
+        eprintln!("\t{} bench <year> <question> <part> [--input <file> | --test[=N]]", $a.bin_name);
+        eprintln!("\t\t[--iterations <N>] [--warmup <N>] [--json]");
+        process::exit(ExitCode::Usage as i32)
     });
 }
 
+// Where frames go with --trace: redrawn in the terminal, or written to a
+// directory.
+enum TraceTo {
+    Terminal,
+    Directory(PathBuf),
+}
+
+// Pause between frames when tracing to the terminal.
+const FRAME_DELAY: Duration = Duration::from_millis(50);
+
+struct Flags {
+    source: Source,
+    iterations: usize,
+    warmup: usize,
+    json: bool,
+    trace: Option<TraceTo>,
+}
+
+impl Default for Flags {
+    fn default() -> Self {
+        Self {
+            source: Source::Stdin,
+            iterations: 100,
+            warmup: 5,
+            json: false,
+            trace: None,
+        }
+    }
+}
+
 struct CommandLine {
     bin_name: String,
     arg_iter: env::Args,
@@ -46,60 +77,256 @@ impl CommandLine {
         Self { bin_name, arg_iter }
     }
 
+    fn parse<T: FromStr>(&self, arg_name: &str, arg: &str) -> T
+    where
+        T::Err: Debug,
+    {
+        arg.parse()
+            .unwrap_or_else(|e| show_usage!(&self, "argument <{}>: {:?}", arg_name, e))
+    }
+
     fn parse_next(&mut self, arg_name: &str) -> i32 {
-        if let Some(arg) = self.arg_iter.next() {
-            arg.parse()
-                .unwrap_or_else(|e| show_usage!(&self, "argument <{}>: {:?}", arg_name, e))
-        } else {
+        let Some(arg) = self.arg_iter.next() else {
             show_usage!(&self, "argument <{}> not provided", arg_name)
+        };
+        self.parse(arg_name, &arg)
+    }
+
+    fn parse_optional(&mut self, arg_name: &str) -> Option<i32> {
+        let arg = self.arg_iter.next()?;
+        Some(self.parse(arg_name, &arg))
+    }
+
+    // Returns the value of a `--flag <value>` or `--flag=<value>` argument.
+    fn flag_value(&mut self, arg: &str, flag: &str) -> Option<String> {
+        if arg == flag {
+            let Some(value) = self.arg_iter.next() else {
+                show_usage!(&self, "flag {} requires a value", flag)
+            };
+            Some(value)
+        } else if arg.starts_with(flag) && arg[flag.len()..].starts_with('=') {
+            Some(arg[flag.len() + 1..].to_string())
+        } else {
+            None
         }
     }
+
+    // Without flags, input is read from stdin unless stdin is a terminal.
+    fn parse_flags(&mut self, year: i32, day: i32, bench: bool) -> Flags {
+        let mut flags = Flags::default();
+        let mut source = None;
+        while let Some(arg) = self.arg_iter.next() {
+            let s = if let Some(path) = self.flag_value(&arg, "--input") {
+                Source::File(path.into())
+            } else if arg == "--test" {
+                Source::File(input_path(year, day, Some(1)))
+            } else if let Some(n) = arg.strip_prefix("--test=") {
+                let n = self.parse("N", n);
+                Source::File(input_path(year, day, Some(n)))
+            } else if !bench && arg == "--trace" {
+                flags.trace = Some(TraceTo::Terminal);
+                continue;
+            } else if let (false, Some(dir)) = (bench, arg.strip_prefix("--trace=")) {
+                flags.trace = Some(TraceTo::Directory(dir.into()));
+                continue;
+            } else if bench && arg == "--json" {
+                flags.json = true;
+                continue;
+            } else if let (true, Some(n)) = (bench, self.flag_value(&arg, "--iterations")) {
+                flags.iterations = self.parse("iterations", &n);
+                continue;
+            } else if let (true, Some(n)) = (bench, self.flag_value(&arg, "--warmup")) {
+                flags.warmup = self.parse("warmup", &n);
+                continue;
+            } else {
+                show_usage!(&self, "unexpected argument: {}", arg)
+            };
+            if source.replace(s).is_some() {
+                show_usage!(&self, "only one of --input or --test may be given");
+            }
+        }
+        flags.source = source.unwrap_or_else(|| Source::default_for(year, day));
+        flags
+    }
 }
 
-fn main() {
-    let mut cli = CommandLine::new();
-    let year = cli.parse_next("year");
-    let day = cli.parse_next("question");
-    let part = cli.parse_next("part");
+// Joins an error with all of its sources.
+fn describe(e: &dyn error::Error) -> String {
+    let mut s = e.to_string();
+    let mut source = e.source();
+    while let Some(e) = source {
+        s += ": ";
+        s += &e.to_string();
+        source = e.source();
+    }
+    s
+}
 
-    println!("AoC {} - Day {} - Part {}:", year, day, part);
-
-    let s = io::stdin();
-    let r = s.lock();
-    let result = match (year, day, part) {
-        (2018, 1, 1) => y2018::day1::Sum.run(r),
-        (2018, 1, 2) => y2018::day1::FirstRepeat.run(r),
-        (2018, 2, 1) => y2018::day2::Checksum([2, 3]).run(r),
-        (2018, 2, 2) => y2018::day2::ExactDiff(1).run(r),
-        (2018, 3, 1) => y2018::day3::TotalOverlapping(1000).run(r),
-        (2018, 3, 2) => y2018::day3::NonOverlapping.run(r),
-        (2018, 4, 1) => y2018::day4::SleepyMinute.run(r),
-        (2018, 4, 2) => y2018::day4::SleepyGuard.run(r),
-        (2018, 5, 1) => y2018::day5::FullReact.run(r),
-        (2018, 5, 2) => y2018::day5::BestReact.run(r),
-        (2018, 6, 1) => y2018::day6::LargestFinite(-100, 400).run(r),
-        (2018, 6, 2) => y2018::day6::Close(-100, 400, 10_000).run(r),
-        (2018, 7, 1) => y2018::day7::Order.run(r),
-        (2018, 7, 2) => y2018::day7::Workers(5).run(r),
-        (2018, 8, 1) => y2018::day8::Sum.run(r),
-        (2018, 8, 2) => y2018::day8::Value.run(r),
-        (2018, 9, _) => y2018::day9::WinningScore.run(r),
-        (2018, 10, _) => y2018::day10::Sky(60_000).run(r),
-        (2018, 11, 1) => y2018::day11::LargestPower(300, 3).run(r),
-        (2018, 11, 2) => y2018::day11::Overall(300).run(r),
-        (2018, 12, 1) => y2018::day12::PlantSum(20).run(r),
-        (2018, 13, 1) => y2018::day13::FirstCrash.run(r),
-        (2018, 13, 2) => y2018::day13::LastCart.run(r),
-        (2018, 14, 1) => y2018::day14::ScoreList(10).run(r),
-        (2018, 14, 2) => y2018::day14::FirstOccurance.run(r),
-        _ => Err(Error::Custom("Problem not implemented")),
+// Prints the error under the puzzle heading and exits with its code. The
+// heading already names the puzzle.
+fn fail(e: &Error) -> ! {
+    let description = match e {
+        Error::Solver(_, inner) => describe(&**inner),
+        e => describe(e),
     };
+    println!("\tError: {}", description.replace('\n', "\n\t"));
+    process::exit(e.exit_code() as i32)
+}
+
+fn registry() -> Registry {
+    let mut r = Registry::new();
+    y2017::register(&mut r);
+    y2018::register(&mut r);
+    r
+}
+
+fn list(registry: &Registry, year: Option<i32>) {
+    for (key, _) in registry.iter() {
+        if year.is_none_or(|y| y == key.year) {
+            println!("{key}");
+        }
+    }
+}
+
+// Runs every solver that has a stored input, printing a row per solver.
+fn all(registry: &Registry, year: Option<i32>) {
+    println!(
+        "{:>4} {:>3} {:>4} | {:<30} | {:>10} | {:>10}",
+        "Year", "Day", "Part", "Answer", "Parse", "Solve"
+    );
+    println!("{:-<14}+{:-<32}+{:-<12}+{:-<12}", "", "", "", "");
 
+    let mut total = Duration::default();
+    let mut failure = None;
+    for (key, solver) in registry.iter() {
+        if year.is_some_and(|y| y != key.year) {
+            continue;
+        }
+        let path = input_path(key.year, key.day, None);
+        if !path.exists() {
+            continue;
+        }
+
+        let result = Source::File(path)
+            .open()
+            .and_then(|mut r| solver.solve_timed(&mut r));
+        let (answer, parse, solve) = match result {
+            Ok((answer, t)) => {
+                total += t.parse + t.solve;
+                (
+                    answer,
+                    format!("{:.3?}", t.parse),
+                    format!("{:.3?}", t.solve),
+                )
+            }
+            Err(e) => {
+                failure.get_or_insert_with(|| e.exit_code());
+                // The row already names the puzzle, and only the summary line
+                // fits in the table.
+                let e = match e {
+                    Error::Solver(_, e) => describe(&*e),
+                    e => describe(&e),
+                };
+                let summary = e.lines().next().unwrap_or_default();
+                (format!("Error: {summary}"), String::new(), String::new())
+            }
+        };
+        println!(
+            "{:>4} {:>3} {:>4} | {:<30} | {:>10} | {:>10}",
+            key.year, key.day, key.part, answer, parse, solve
+        );
+    }
+    println!("Total: {total:.3?}");
+
+    if let Some(code) = failure {
+        process::exit(code as i32);
+    }
+}
+
+fn get_solver(registry: &Registry, key: Key) -> &dyn Solver {
+    registry.get(key).unwrap_or_else(|| {
+        println!("\tError: Problem not implemented");
+        process::exit(ExitCode::Usage as i32);
+    })
+}
+
+fn run_bench(registry: &Registry, key: Key, flags: &Flags) {
+    if !flags.json {
+        println!("{key}:");
+    }
+    let solver = get_solver(registry, key);
+
+    let result = flags.source.open().and_then(|mut r| {
+        let mut input = Vec::new();
+        r.read_to_end(&mut input)?;
+        bench(solver, &input, flags.warmup, flags.iterations)
+    });
     match result {
-        Ok(o) => println!("\t{}", o),
+        Ok(b) if flags.json => println!("{}", b.to_json(key)),
+        Ok(b) => print!("{b}"),
         Err(e) => {
-            println!("\tError: {:?}", e);
-            process::exit(1);
+            if flags.json {
+                println!("{key}:");
+            }
+            fail(&e)
+        }
+    }
+}
+
+fn run(registry: &Registry, key: Key, flags: &Flags) {
+    println!("{key}:");
+    let solver = get_solver(registry, key);
+
+    let result = flags.source.open().and_then(|mut r| match &flags.trace {
+        None => solver.solve(&mut r),
+        Some(TraceTo::Terminal) => {
+            let mut terminal = Terminal { delay: FRAME_DELAY };
+            solver.solve_traced(&mut r, &mut terminal)
+        }
+        Some(TraceTo::Directory(dir)) => {
+            let mut frames = Frames::new(dir)?;
+            let answer = solver.solve_traced(&mut r, &mut frames)?;
+            frames.finish()?;
+            Ok(answer)
         }
+    });
+    match result {
+        Ok(o) => println!("\t{o}"),
+        Err(e) => fail(&e),
+    }
+}
+
+fn main() {
+    let registry = registry();
+    let mut cli = CommandLine::new();
+
+    let first = cli
+        .arg_iter
+        .next()
+        .unwrap_or_else(|| show_usage!(&cli, "argument <year> not provided"));
+
+    if first == "list" {
+        let year = cli.parse_optional("year");
+        return list(&registry, year);
+    }
+    if first == "all" {
+        let year = cli.parse_optional("year");
+        return all(&registry, year);
+    }
+
+    let bench = first == "bench";
+    let year = if bench {
+        cli.parse_next("year")
+    } else {
+        cli.parse("year", &first)
     };
+    let day = cli.parse_next("question");
+    let part = cli.parse_next("part");
+    let key = Key { year, day, part };
+    let flags = cli.parse_flags(year, day, bench);
+    if bench {
+        run_bench(&registry, key, &flags);
+    } else {
+        run(&registry, key, &flags);
+    }
 }
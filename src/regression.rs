@@ -0,0 +1,122 @@
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use crate::{
+    registry,
+    util::{input_path, Key, Source},
+};
+
+// Maps (year, input file stem, part) to the expected answer.
+type Answers = BTreeMap<(i32, String, i32), String>;
+
+// Reads the subset of TOML used by `input/answers.toml`: `[<year>.<file>]`
+// tables containing `part<N> = "<answer>"` entries.
+fn load_answers() -> Answers {
+    let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "input", "answers.toml"]
+        .iter()
+        .collect();
+    let contents = fs::read_to_string(&path).expect("Unable to read answers.toml");
+
+    let mut answers = Answers::new();
+    let mut table = None;
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        let bad_line = || -> ! { panic!("answers.toml:{}: malformed line {:?}", i + 1, line) };
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with('[') && line.ends_with(']') {
+            let mut parts = line[1..line.len() - 1].splitn(2, '.');
+            let year = parts.next().and_then(|y| y.parse().ok());
+            match (year, parts.next()) {
+                (Some(year), Some(file)) => table = Some((year, file.to_string())),
+                _ => bad_line(),
+            }
+            continue;
+        }
+
+        let mut parts = line.splitn(2, '=').map(str::trim);
+        let part = parts.next().unwrap();
+        let part = match (part.starts_with("part"), table.as_ref()) {
+            (true, Some(_)) => part["part".len()..].parse().unwrap_or_else(|_| bad_line()),
+            _ => bad_line(),
+        };
+        let value = parts.next().unwrap_or_else(|| bad_line());
+        if value.len() < 2 || !value.starts_with('"') || !value.ends_with('"') {
+            bad_line()
+        }
+        let (year, file) = table.clone().unwrap();
+        answers.insert((year, file, part), value[1..value.len() - 1].to_string());
+    }
+    answers
+}
+
+// "day13_test2" -> 13
+fn day_of(file: &str) -> i32 {
+    let digits: String = file
+        .trim_start_matches("day")
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+    digits.parse().expect("Input files are named day<N>*")
+}
+
+#[test]
+fn expected_answers() {
+    let registry = registry();
+    let mut failures = Vec::new();
+
+    for ((year, file, part), expected) in load_answers() {
+        let key = Key {
+            year,
+            day: day_of(&file),
+            part,
+        };
+        let Some(solver) = registry.get(key) else {
+            failures.push(format!("{key} ({file}): not registered"));
+            continue;
+        };
+        let path: PathBuf = [
+            env!("CARGO_MANIFEST_DIR"),
+            "input",
+            &year.to_string(),
+            &format!("{file}.txt"),
+        ]
+        .iter()
+        .collect();
+
+        match Source::File(path)
+            .open()
+            .and_then(|mut r| solver.solve(&mut r))
+        {
+            Ok(ref actual) if *actual == expected => {}
+            Ok(actual) => failures.push(format!(
+                "{key} ({file}): expected {expected:?}, got {actual:?}"
+            )),
+            Err(e) => failures.push(format!("{key} ({file}): {e:?}")),
+        }
+    }
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+#[test]
+fn stored_inputs_have_answers() {
+    let answers = load_answers();
+    let missing: Vec<_> = registry()
+        .iter()
+        .map(|(key, _)| key)
+        .filter(|key| input_path(key.year, key.day, None).exists())
+        .filter(|key| {
+            !answers
+                .keys()
+                .any(|(y, file, p)| (*y, day_of(file), *p) == (key.year, key.day, key.part))
+        })
+        .map(|key| key.to_string())
+        .collect();
+
+    assert!(
+        missing.is_empty(),
+        "No expected answer for:\n{}",
+        missing.join("\n")
+    );
+}
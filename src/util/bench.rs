@@ -0,0 +1,149 @@
+use std::{fmt, fmt::Write, time::Duration};
+
+use crate::util::{Error, Key, Result, Solver, Timing};
+
+#[derive(Copy, Clone, Debug)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    // Samples must be non-empty.
+    fn new(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        let n = samples.len();
+        let median = if n.is_multiple_of(2) {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        } else {
+            samples[n / 2]
+        };
+
+        let secs: Vec<f64> = samples.iter().map(Duration::as_secs_f64).collect();
+        // Sample counts are far too small to lose precision.
+        #[allow(clippy::cast_precision_loss)]
+        let count = n as f64;
+        let mean = secs.iter().sum::<f64>() / count;
+        let variance = secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / count;
+
+        Self {
+            min: samples[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+
+    fn to_json(self) -> String {
+        format!(
+            r#"{{"min_ns": {}, "median_ns": {}, "mean_ns": {}, "stddev_ns": {}}}"#,
+            self.min.as_nanos(),
+            self.median.as_nanos(),
+            self.mean.as_nanos(),
+            self.stddev.as_nanos()
+        )
+    }
+}
+
+pub struct Bench {
+    pub answer: String,
+    pub warmup: usize,
+    pub iterations: usize,
+    pub parse: Stats,
+    pub solve: Stats,
+    pub total: Stats,
+}
+
+/// Runs the solver `warmup` times untimed, then `iterations` times timed.
+/// Every run reads from the same in-memory input, so file IO is not measured.
+pub fn bench(solver: &dyn Solver, input: &[u8], warmup: usize, iterations: usize) -> Result<Bench> {
+    if iterations == 0 {
+        return Err(Error::Custom("At least one iteration is required"));
+    }
+    for _ in 0..warmup {
+        solver.solve(&mut &input[..])?;
+    }
+
+    let mut answer = String::new();
+    let mut timings = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let (a, t) = solver.solve_timed(&mut &input[..])?;
+        answer = a;
+        timings.push(t);
+    }
+
+    let stats = |f: fn(&Timing) -> Duration| Stats::new(timings.iter().map(f).collect());
+    Ok(Bench {
+        answer,
+        warmup,
+        iterations,
+        parse: stats(|t| t.parse),
+        solve: stats(|t| t.solve),
+        total: stats(|t| t.parse + t.solve),
+    })
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if c.is_control() => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+impl Bench {
+    pub fn to_json(&self, key: Key) -> String {
+        format!(
+            r#"{{"year": {}, "day": {}, "part": {}, "answer": {}, "warmup": {}, "iterations": {}, "parse": {}, "solve": {}, "total": {}}}"#,
+            key.year,
+            key.day,
+            key.part,
+            json_string(&self.answer),
+            self.warmup,
+            self.iterations,
+            self.parse.to_json(),
+            self.solve.to_json(),
+            self.total.to_json()
+        )
+    }
+}
+
+impl fmt::Display for Bench {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "\t{}", self.answer)?;
+        writeln!(
+            f,
+            "\t{} iterations after {} warm-up runs",
+            self.iterations, self.warmup
+        )?;
+        writeln!(
+            f,
+            "\t{:<6} {:>12} {:>12} {:>12} {:>12}",
+            "", "min", "median", "mean", "stddev"
+        )?;
+        for (name, s) in &[
+            ("parse", self.parse),
+            ("solve", self.solve),
+            ("total", self.total),
+        ] {
+            writeln!(
+                f,
+                "\t{:<6} {:>12} {:>12} {:>12} {:>12}",
+                name,
+                format!("{:.3?}", s.min),
+                format!("{:.3?}", s.median),
+                format!("{:.3?}", s.mean),
+                format!("{:.3?}", s.stddev)
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,218 @@
+//! Finding cycles in the states of long-running simulations.
+//!
+//! All of these step from an initial state (step 0) until some state repeats.
+//! [`brent`] and [`floyd`] loop forever if the sequence never repeats, while
+//! [`hashed`] and [`Drift`] give up after a step limit.
+
+use std::{collections::HashMap, hash::Hash};
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Cycle {
+    /// The first step whose state is part of the cycle.
+    pub start: usize,
+    pub len: usize,
+}
+
+impl Cycle {
+    /// The step before the end of the first cycle whose state matches step n.
+    pub const fn equivalent(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.len
+        }
+    }
+}
+
+/// Brent's algorithm, comparing states for equality while only keeping two
+/// states in memory.
+pub fn brent<S: Clone + Eq>(initial: S, step: impl Fn(&S) -> S) -> Cycle {
+    // Find the length by moving the hare ahead of a tortoise that teleports
+    // to the hare at every power of two.
+    let mut power = 1;
+    let mut len = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == len {
+            tortoise = hare.clone();
+            power *= 2;
+            len = 0;
+        }
+        hare = step(&hare);
+        len += 1;
+    }
+
+    // Then find the start with two pointers len steps apart.
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..len {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+    Cycle { start, len }
+}
+
+/// Floyd's tortoise and hare algorithm, comparing states for equality while
+/// only keeping two states in memory. Usually slower than [`brent`].
+#[allow(dead_code)]
+pub fn floyd<S: Clone + Eq>(initial: S, step: impl Fn(&S) -> S) -> Cycle {
+    // The hare moves twice as fast, so they meet at a multiple of the length.
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&step(&hare));
+    }
+
+    // Restarting the tortoise, they now meet at the start of the cycle.
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut len = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        len += 1;
+    }
+    Cycle { start, len }
+}
+
+/// Remembers every state seen, so each step is only computed once. States
+/// are considered the same if `normalize` maps them to the same key.
+///
+/// Returns the cycle along with every state up to the first repeat, or no
+/// cycle and the first `limit + 1` states if none repeat by step `limit`.
+pub fn hashed<S, K: Eq + Hash>(
+    initial: S,
+    limit: usize,
+    mut step: impl FnMut(&S) -> S,
+    normalize: impl Fn(&S) -> K,
+) -> (Option<Cycle>, Vec<S>) {
+    let mut seen = HashMap::new();
+    let mut states = vec![initial];
+    loop {
+        let n = states.len() - 1;
+        if let Some(start) = seen.insert(normalize(&states[n]), n) {
+            states.pop();
+            let cycle = Cycle {
+                start,
+                len: n - start,
+            };
+            return (Some(cycle), states);
+        }
+        if n == limit {
+            return (None, states);
+        }
+        let next = step(&states[n]);
+        states.push(next);
+    }
+}
+
+/// A value computed from each state, for sequences where the normalized
+/// state cycles but the value changes by a fixed amount every cycle (say, a
+/// pattern that repeats while moving along).
+pub struct Drift {
+    /// None if no state repeated within the step limit.
+    pub cycle: Option<Cycle>,
+    // Values up to the limit, or for two full cycles if that comes first, so
+    // the drift of each step in the cycle is known.
+    values: Vec<i64>,
+}
+
+impl Drift {
+    /// Steps at most `limit` times, stopping sooner once the drift is known.
+    pub fn new<S, K: Eq + Hash>(
+        initial: S,
+        limit: usize,
+        mut step: impl FnMut(&S) -> S,
+        normalize: impl Fn(&S) -> K,
+        value: impl Fn(&S) -> i64,
+    ) -> Self {
+        let (cycle, mut states) = hashed(initial, limit, &mut step, normalize);
+        if let Some(cycle) = cycle {
+            let len = (cycle.start + 2 * cycle.len).min(limit + 1);
+            while states.len() < len {
+                let next = step(states.last().unwrap());
+                states.push(next);
+            }
+        }
+        let values = states.iter().map(value).collect();
+        Self { cycle, values }
+    }
+
+    /// The value of the state after n steps, for any n up to the limit.
+    pub fn value_at(&self, n: usize) -> i64 {
+        if let Some(&v) = self.values.get(n) {
+            return v;
+        }
+        let cycle = self.cycle.expect("Step is past the limit");
+        let i = cycle.equivalent(n);
+        let cycles = ((n - i) / cycle.len) as i64;
+        let drift = self.values[i + cycle.len] - self.values[i];
+        self.values[i] + cycles * drift
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Powers of two mod 11, which cycle through 10 values, after a lead-in
+    // of 100 -> 101.
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    fn step(&x: &u32) -> u32 {
+        match x {
+            100 => 101,
+            101 => 1,
+            x => (2 * x) % 11,
+        }
+    }
+
+    #[test]
+    fn algorithms_agree() {
+        let expected = Cycle { start: 2, len: 10 };
+        assert_eq!(brent(100, step), expected);
+        assert_eq!(floyd(100, step), expected);
+        let (cycle, states) = hashed(100, usize::MAX, step, |&x| x);
+        assert_eq!(cycle, Some(expected));
+        assert_eq!(states.len(), 12);
+        assert_eq!(expected.equivalent(25), 5);
+    }
+
+    #[test]
+    fn linear_drift() {
+        // (position, phase): the phase cycles 0, 1, 2 while position moves
+        // by 3 every cycle.
+        let step = |&(pos, phase): &(i64, i64)| (pos + phase, (phase + 1) % 3);
+        let drift = Drift::new(
+            (0, 0),
+            3_000_000_000,
+            step,
+            |&(_, phase)| phase,
+            |&(pos, _)| pos,
+        );
+        assert_eq!(drift.cycle, Some(Cycle { start: 0, len: 3 }));
+        let direct = (0..100).fold((0, 0), |s, _| step(&s));
+        assert_eq!(drift.value_at(100), direct.0);
+        assert_eq!(drift.value_at(3_000_000_000), 3_000_000_000);
+    }
+
+    #[test]
+    fn step_limit() {
+        let (cycle, states) = hashed(0, 5, |&x| x + 1, |&x| x);
+        assert_eq!((cycle, states), (None, vec![0, 1, 2, 3, 4, 5]));
+        let drift = Drift::new(0, 20, |&x| x + 1, |&x| x, |&x| x * x);
+        assert_eq!(drift.value_at(20), 400);
+    }
+}
@@ -1,80 +1,130 @@
-use std::{
-    fmt, io, num, option,
-    str::{FromStr, Utf8Error},
-};
+use std::{error, fmt, io, num, path::PathBuf, str::Utf8Error};
 
-use nom::{error_position, IResult};
+use crate::util::{parse::ParseError, Key};
+
+/// Where in the input an error occurred.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    /// Line and column both start at 1.
+    pub line: usize,
+    pub column: usize,
+    /// Byte offset from the start of the input.
+    pub offset: usize,
+    /// Text of the offending line.
+    pub text: String,
+}
+
+// Renders the line with a caret under the error's column.
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let gutter = self.line.to_string().len();
+        writeln!(f, "{} | {}", self.line, self.text)?;
+        write!(
+            f,
+            "{:gutter$} | {:>col$}",
+            "",
+            "^",
+            gutter = gutter,
+            col = self.column
+        )
+    }
+}
 
 #[derive(Debug)]
 pub enum Error {
     IO(io::Error),
-    Nom(String),
+    MissingInput(PathBuf),
+    Syntax(ParseError),
+    Located(Location, Box<Self>),
     Parse(String),
     Custom(&'static str),
+    /// Like `Custom`, for messages built at runtime.
+    Message(String),
+    /// A failure while running the solver for the given puzzle.
+    Solver(Key, Box<Self>),
+    /// A failure writing to the given file or directory.
+    Output(PathBuf, io::Error),
+}
+
+/// Process exit codes for each kind of failure.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ExitCode {
+    Usage = 2,
+    Input = 3,
+    Parse = 4,
+    Solve = 5,
+    Output = 6,
+}
+
+impl Error {
+    pub fn exit_code(&self) -> ExitCode {
+        match self {
+            Self::IO(_) | Self::MissingInput(_) => ExitCode::Input,
+            Self::Syntax(_) | Self::Located(..) | Self::Parse(_) => ExitCode::Parse,
+            Self::Custom(_) | Self::Message(_) => ExitCode::Solve,
+            Self::Solver(_, e) => e.exit_code(),
+            Self::Output(..) => ExitCode::Output,
+        }
+    }
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?}", self)
+        match self {
+            Self::IO(_) => f.write_str("Unable to read input"),
+            Self::MissingInput(path) => write!(f, "Input file {} not found", path.display()),
+            Self::Syntax(e) => write!(f, "column {}: {}", e.offset + 1, e),
+            Self::Located(l, e) => {
+                write!(f, "line {}, column {}: ", l.line, l.column)?;
+                match &**e {
+                    // The location already accounts for the column.
+                    Self::Syntax(e) => write!(f, "{e}")?,
+                    e => write!(f, "{e}")?,
+                }
+                write!(f, "\n{l}")
+            }
+            Self::Parse(s) | Self::Message(s) => f.write_str(s),
+            Self::Custom(s) => f.write_str(s),
+            Self::Solver(key, _) => write!(f, "{key}"),
+            Self::Output(path, _) => write!(f, "Unable to write {}", path.display()),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::IO(e) | Self::Output(_, e) => Some(e),
+            Self::Solver(_, e) => Some(&**e),
+            // The inner message is already part of the location's message.
+            Self::Located(_, e) => e.source(),
+            _ => None,
+        }
     }
 }
 
 impl From<Utf8Error> for Error {
     fn from(e: Utf8Error) -> Self {
-        Error::Parse(e.to_string())
+        Self::Parse(e.to_string())
     }
 }
 
 impl From<io::Error> for Error {
     fn from(e: io::Error) -> Self {
-        Error::IO(e)
+        Self::IO(e)
     }
 }
 
 impl From<num::TryFromIntError> for Error {
     fn from(e: num::TryFromIntError) -> Self {
-        Error::Parse(e.to_string())
+        Self::Parse(e.to_string())
     }
 }
 
 impl From<num::ParseIntError> for Error {
     fn from(e: num::ParseIntError) -> Self {
-        Error::Parse(e.to_string())
-    }
-}
-
-impl From<option::NoneError> for Error {
-    fn from(_: option::NoneError) -> Self {
-        Error::Custom("Unexpected option unwrap")
+        Self::Parse(e.to_string())
     }
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
-
-pub fn to_result<T>(r: IResult<&str, T>) -> Result<T> {
-    match r {
-        Ok(("", output)) => Ok(output),
-        Ok((input, _)) => Err(Error::Nom(format!("{} leftover chars", input.len()))),
-        Err(e) => Err(Error::Nom(e.to_string())),
-    }
-}
-
-pub fn trim_parse<T: FromStr>(s: &str) -> std::result::Result<T, T::Err> {
-    s.trim().parse()
-}
-
-// Nom parser for any itegral type written in ascii.
-pub fn number<O: FromStr>(input: &str) -> IResult<&str, O> {
-    let (input, output) = match nom::digit(input) {
-        Ok(io) => io,
-        Err(nom::Err::Incomplete(_)) => ("", input),
-        Err(e) => return Err(e),
-    };
-    match output.parse() {
-        Ok(n) => Ok((input, n)),
-        Err(_) => Err(nom::Err::Error(error_position!(
-            output,
-            nom::ErrorKind::Digit
-        ))),
-    }
-}
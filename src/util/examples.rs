@@ -0,0 +1,29 @@
+/// Declares puzzle examples for the solvers in a module. Each entry becomes a
+/// `#[test]` that runs the solver on an in-memory input:
+///
+/// ```ignore
+/// examples! {
+///     sum_mixed: Sum, "+1\n-2\n+3\n+1" => 3;
+///     first_crash: FirstCrash, include_str!("../../input/2018/day13_test.txt") => "(7,3)";
+/// }
+/// ```
+#[macro_export]
+macro_rules! examples {
+    ($($name:ident: $solver:expr, $input:expr => $expected:expr;)*) => {
+        #[cfg(test)]
+        mod examples {
+            #[allow(unused_imports)]
+            use super::*;
+            use $crate::util::Solver;
+
+            $(
+                #[test]
+                fn $name() {
+                    let input: &str = $input;
+                    let actual = $solver.solve(&mut input.as_bytes()).unwrap();
+                    assert_eq!(actual, $expected.to_string());
+                }
+            )*
+        }
+    };
+}
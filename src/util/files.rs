@@ -0,0 +1,56 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, IsTerminal},
+    path::PathBuf,
+};
+
+use crate::util::{Error, Result};
+
+/// Path of the input for a day: `input/<year>/day<day>.txt`, or one of the
+/// `day<day>_test<N>.txt` variants. The first test file has no number, so
+/// `test == Some(1)` refers to `day<day>_test.txt`.
+pub fn input_path(year: i32, day: i32, test: Option<u32>) -> PathBuf {
+    let name = match test {
+        None => format!("day{day}.txt"),
+        Some(1) => format!("day{day}_test.txt"),
+        Some(n) => format!("day{day}_test{n}.txt"),
+    };
+    [
+        env!("CARGO_MANIFEST_DIR"),
+        "input",
+        &year.to_string(),
+        &name,
+    ]
+    .iter()
+    .collect()
+}
+
+pub enum Source {
+    Stdin,
+    File(PathBuf),
+}
+
+impl Source {
+    /// Reads from stdin if something is piped in, otherwise from the stored
+    /// input for the given day.
+    pub fn default_for(year: i32, day: i32) -> Self {
+        if io::stdin().is_terminal() {
+            Self::File(input_path(year, day, None))
+        } else {
+            Self::Stdin
+        }
+    }
+
+    pub fn open(&self) -> Result<Box<dyn BufRead>> {
+        match self {
+            Self::Stdin => Ok(Box::new(io::stdin().lock())),
+            Self::File(path) => match File::open(path) {
+                Ok(f) => Ok(Box::new(BufReader::new(f))),
+                Err(ref e) if e.kind() == io::ErrorKind::NotFound => {
+                    Err(Error::MissingInput(path.clone()))
+                }
+                Err(e) => Err(Error::IO(e)),
+            },
+        }
+    }
+}
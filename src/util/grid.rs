@@ -0,0 +1,308 @@
+//! Dense, row-major 2D grids indexed by [`Point`].
+
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use euclid::Vector2D;
+
+use crate::util::{Error, Location, Result};
+
+pub type Point = Vector2D<i32>;
+
+/// Offsets to the orthogonally adjacent points, in reading order.
+#[allow(dead_code)]
+pub const NEIGHBORS4: [(i32, i32); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+/// Offsets to the orthogonally and diagonally adjacent points, in reading
+/// order.
+#[allow(dead_code)]
+pub const NEIGHBORS8: [(i32, i32); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Point) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Point::new(x as i32, y as i32)))
+            .map(&mut f)
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Builds a grid from rows of text, decoding each character into a cell.
+    /// All rows must be the same length.
+    #[allow(dead_code)]
+    pub fn parse(
+        lines: impl IntoIterator<Item = String>,
+        decode: impl FnMut(char) -> Result<T>,
+    ) -> Result<Self> {
+        let (cells, lens) = Self::parse_rows(lines, decode, true)?;
+        Ok(Self {
+            width: lens.first().copied().unwrap_or_default(),
+            height: lens.len(),
+            cells,
+        })
+    }
+
+    /// Like [`Grid::parse`], but pads rows shorter than the longest with
+    /// `fill`, for text whose trailing spaces may have been trimmed.
+    pub fn parse_padded(
+        lines: impl IntoIterator<Item = String>,
+        fill: T,
+        decode: impl FnMut(char) -> Result<T>,
+    ) -> Result<Self>
+    where
+        T: Clone,
+    {
+        let (cells, lens) = Self::parse_rows(lines, decode, false)?;
+        let width = lens.iter().copied().max().unwrap_or_default();
+        let mut padded = Vec::with_capacity(width * lens.len());
+        let mut cells = cells.into_iter();
+        for &len in &lens {
+            padded.extend(cells.by_ref().take(len));
+            padded.resize(padded.len() + width - len, fill.clone());
+        }
+        Ok(Self {
+            width,
+            height: lens.len(),
+            cells: padded,
+        })
+    }
+
+    // The cells of every row, along with the length of each row.
+    fn parse_rows(
+        lines: impl IntoIterator<Item = String>,
+        mut decode: impl FnMut(char) -> Result<T>,
+        same_width: bool,
+    ) -> Result<(Vec<T>, Vec<usize>)> {
+        let mut lens: Vec<usize> = Vec::new();
+        let mut offset = 0;
+        let mut cells = Vec::new();
+        for line in lines {
+            // Columns count characters, offsets count bytes.
+            let located = |column: usize, byte: usize, e: Error| {
+                let location = Location {
+                    line: lens.len() + 1,
+                    column: column + 1,
+                    offset: offset + byte,
+                    text: line.clone(),
+                };
+                Error::Located(location, Box::new(e))
+            };
+
+            let mut len = 0;
+            for (i, c) in line.char_indices() {
+                cells.push(decode(c).map_err(|e| located(len, i, e))?);
+                len += 1;
+            }
+            match lens.first() {
+                Some(&width) if same_width && width != len => {
+                    let e = Error::Parse(format!("Row has {len} cells, expected {width}"));
+                    return Err(located(len, line.len(), e));
+                }
+                _ => lens.push(len),
+            }
+            offset += line.len() + 1;
+        }
+        Ok((cells, lens))
+    }
+
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        (0..self.width as i32).contains(&p.x) && (0..self.height as i32).contains(&p.y)
+    }
+
+    fn index_of(&self, p: Point) -> Option<usize> {
+        if self.contains(p) {
+            Some(p.y as usize * self.width + p.x as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.index_of(p).map(|i| &self.cells[i])
+    }
+
+    /// All points in the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point::new(x as i32, y as i32)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(move |y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} out of bounds", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    fn neighbors<'a>(
+        &'a self,
+        p: Point,
+        offsets: &'static [(i32, i32)],
+    ) -> impl Iterator<Item = Point> + 'a {
+        offsets
+            .iter()
+            .map(move |&(dx, dy)| p + Point::new(dx, dy))
+            .filter(move |&n| self.contains(n))
+    }
+
+    /// Orthogonally adjacent points within the grid.
+    #[allow(dead_code)]
+    pub fn neighbors4(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbors(p, &NEIGHBORS4)
+    }
+
+    /// Orthogonally and diagonally adjacent points within the grid.
+    #[allow(dead_code)]
+    pub fn neighbors8(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbors(p, &NEIGHBORS8)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+    fn index(&self, p: Point) -> &T {
+        self.get(p)
+            .unwrap_or_else(|| panic!("{:?} out of bounds", p))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        match self.index_of(p) {
+            Some(i) => &mut self.cells[i],
+            None => panic!("{:?} out of bounds", p),
+        }
+    }
+}
+
+// Renders each row on its own line.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(s: &str) -> Result<Grid<u32>> {
+        Grid::parse(s.lines().map(String::from), |c| {
+            c.to_digit(10).ok_or(Error::Custom("not a digit"))
+        })
+    }
+
+    #[test]
+    fn parse_and_render() {
+        let g = digits("123\n456").unwrap();
+        assert_eq!((g.width(), g.height()), (3, 2));
+        assert_eq!(g[Point::new(2, 1)], 6);
+        assert_eq!(g.get(Point::new(3, 0)), None);
+        assert_eq!(g.column(1).collect::<Vec<_>>(), [&2, &5]);
+        assert_eq!(g.to_string(), "123\n456");
+    }
+
+    #[test]
+    fn parse_padded() {
+        let g = Grid::parse_padded("12\n3\n456".lines().map(String::from), 0, |c| {
+            c.to_digit(10).ok_or(Error::Custom("not a digit"))
+        })
+        .unwrap();
+        assert_eq!(g.to_string(), "120\n300\n456");
+    }
+
+    #[test]
+    fn parse_errors() {
+        match digits("12\n3x") {
+            Err(Error::Located(l, _)) => assert_eq!((l.line, l.column, l.offset), (2, 2, 4)),
+            r => panic!("expected located error, got {:?}", r),
+        }
+        assert!(digits("12\n345").is_err());
+
+        // Columns count characters, even when they take several bytes.
+        match Grid::parse("ab\n\u{e9}\u{e8}\u{ea}".lines().map(String::from), Ok) {
+            Err(Error::Located(l, _)) => assert_eq!((l.line, l.column, l.offset), (2, 4, 9)),
+            r => panic!("expected located error, got {:?}", r),
+        }
+    }
+
+    #[test]
+    fn neighbors() {
+        let g = Grid::filled(3, 3, ());
+        assert_eq!(g.neighbors4(Point::new(1, 1)).count(), 4);
+        assert_eq!(g.neighbors8(Point::new(1, 1)).count(), 8);
+        let corner: Vec<_> = g.neighbors4(Point::new(0, 0)).collect();
+        assert_eq!(corner, [Point::new(1, 0), Point::new(0, 1)]);
+        assert_eq!(g.neighbors8(Point::new(2, 2)).count(), 3);
+    }
+}
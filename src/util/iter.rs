@@ -1,5 +1,5 @@
 use std::{
-    collections::{hash_map::Entry, HashMap},
+    collections::{HashMap, HashSet},
     hash::Hash,
     vec,
 };
@@ -10,7 +10,7 @@ use crate::util::{Error, Result};
 #[derive(Clone)]
 pub struct Duplicates<I: Iterator> {
     iter: I,
-    seen: HashMap<I::Item, ()>,
+    seen: HashSet<I::Item>,
 }
 
 impl<I: Iterator> Iterator for Duplicates<I>
@@ -20,13 +20,11 @@ where
     type Item = I::Item;
 
     #[inline]
-    // TODO: use Set Entry API (https://github.com/rust-lang/rfcs/issues/1490)
     fn next(&mut self) -> Option<I::Item> {
         loop {
-            match self.seen.entry(self.iter.next()?) {
-                Entry::Occupied(o) => return Some(o.replace_key()),
-                Entry::Vacant(v) => v.insert(()),
-            };
+            if let Some(seen) = self.seen.replace(self.iter.next()?) {
+                return Some(seen);
+            }
         }
     }
 }
@@ -50,25 +48,10 @@ pub trait Extra: Iterator + Sized {
     {
         Duplicates {
             iter: self,
-            seen: HashMap::new(),
+            seen: HashSet::new(),
         }
     }
 
-    fn only_uniques_by<V: Eq + Hash>(self, mut f: impl FnMut(&Self::Item) -> V) -> Vec<Self::Item> {
-        let mut seen = HashMap::new();
-        for item in self {
-            match seen.entry(f(&item)) {
-                Entry::Occupied(mut o) => {
-                    o.insert(None);
-                }
-                Entry::Vacant(v) => {
-                    v.insert(Some(item));
-                }
-            };
-        }
-        seen.into_iter().flat_map(|(_, v)| v).collect()
-    }
-
     fn frequencies(self) -> HashMap<Self::Item, usize>
     where
         Self::Item: Eq + Hash,
@@ -1,9 +1,23 @@
+mod bench;
+pub use self::bench::*;
+pub mod cycle;
 mod error;
 pub use self::error::*;
+mod examples;
+mod files;
+pub use self::files::*;
+pub mod grid;
 mod iter;
 pub use self::iter::Extra as IterExtra;
+pub mod ocr;
+pub mod parse;
+mod registry;
+pub use self::registry::*;
 mod runner;
 pub use self::runner::*;
+pub mod schedule;
+pub mod summed_area;
+pub mod trace;
 
 use std::{
     cmp::{max, min},
@@ -13,8 +27,21 @@ use std::{
 pub fn overlap<Idx: Ord>(a: Range<Idx>, b: Range<Idx>) -> Option<Range<Idx>> {
     let start = max(a.start, b.start);
     let end = min(a.end, b.end);
-    if start > end {
+    if start >= end {
         return None;
     }
     Some(Range { start, end })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn touching_ranges_do_not_overlap() {
+        assert_eq!(overlap(0..3, 1..5), Some(1..3));
+        assert_eq!(overlap(0..3, 3..5), None);
+        assert_eq!(overlap(3..5, 0..3), None);
+        assert_eq!(overlap(0..3, 5..7), None);
+    }
+}
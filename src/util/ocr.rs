@@ -0,0 +1,202 @@
+//! Reading the block letters that some puzzles draw with lit pixels.
+//!
+//! Advent of Code uses two fonts: letters 6 pixels tall (usually 4 wide) and
+//! letters 10 pixels tall (usually 6 wide). Letters are split wherever there
+//! is an empty column, so the spacing between them doesn't matter.
+
+use itertools::Itertools;
+
+use crate::util::{
+    grid::{Grid, Point},
+    Error, Result,
+};
+
+type Font = &'static [(char, &'static [&'static str])];
+
+#[rustfmt::skip]
+const FONT_4X6: Font = &[
+    ('A', &[".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', &["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', &[".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', &["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', &["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', &[".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', &["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', &["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', &["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', &["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', &["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', &[".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', &["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', &["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', &[".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', &["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', &["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', &["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+#[rustfmt::skip]
+const FONT_6X10: Font = &[
+    ('A', &["..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#"]),
+    ('B', &["#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#", "#####."]),
+    ('C', &[".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#", ".####."]),
+    ('E', &["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('F', &["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('G', &[".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+    ('H', &["#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#", "#....#"]),
+    ('J', &["...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+    ('K', &["#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+    ('L', &["#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('N', &["#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+    ('P', &["#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('R', &["#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+    ('X', &["#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+    ('Z', &["######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....", "######"]),
+];
+
+const fn font(height: usize) -> Option<Font> {
+    match height {
+        6 => Some(FONT_4X6),
+        10 => Some(FONT_6X10),
+        _ => None,
+    }
+}
+
+/// Whether there is a font for letters this many pixels tall.
+pub const fn has_font(height: usize) -> bool {
+    font(height).is_some()
+}
+
+/// Reads the letters drawn by a set of lit points.
+pub fn read_points(points: impl IntoIterator<Item = Point>) -> Result<String> {
+    let points: Vec<_> = points.into_iter().collect();
+    let (Some((x_min, x_max)), Some((y_min, y_max))) = (
+        points.iter().map(|p| p.x).minmax().into_option(),
+        points.iter().map(|p| p.y).minmax().into_option(),
+    ) else {
+        return read(&Grid::filled(0, 0, false));
+    };
+    let (min, max) = (Point::new(x_min, y_min), Point::new(x_max, y_max));
+    let size = max - min + Point::new(1, 1);
+    let mut grid = Grid::filled(size.x as usize, size.y as usize, false);
+    for p in points {
+        grid[p - min] = true;
+    }
+    read(&grid)
+}
+
+/// Reads the letters drawn by the lit cells of a grid. Blank rows above and
+/// below the letters are ignored.
+pub fn read(grid: &Grid<bool>) -> Result<String> {
+    let lit_rows: Vec<_> = grid
+        .rows()
+        .enumerate()
+        .filter(|(_, row)| row.contains(&true))
+        .map(|(y, _)| y)
+        .collect();
+    let (top, bottom) = match (lit_rows.first(), lit_rows.last()) {
+        (Some(&top), Some(&bottom)) => (top, bottom + 1),
+        _ => return Err(Error::Custom("No letters to read")),
+    };
+    let height = bottom - top;
+    let Some(font) = font(height) else {
+        return Err(Error::Message(format!(
+            "Letters are {height} pixels tall, expected 6 or 10"
+        )));
+    };
+
+    let lit = |x: usize, y: usize| grid.row(y)[x];
+    // Rows outside top..bottom are blank anyway.
+    let blank_column = |x: usize| !grid.column(x).any(|&lit| lit);
+    let mut letters = String::new();
+    let mut unknown = Vec::new();
+    let mut x = 0;
+    while x < grid.width() {
+        if blank_column(x) {
+            x += 1;
+            continue;
+        }
+        let left = x;
+        while x < grid.width() && !blank_column(x) {
+            x += 1;
+        }
+        let glyph: Vec<String> = (top..bottom)
+            .map(|y| {
+                (left..x)
+                    .map(|x| if lit(x, y) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+        if let Some(&(c, _)) = font.iter().find(|(_, g)| g.iter().eq(glyph.iter())) {
+            letters.push(c);
+        } else {
+            letters.push('?');
+            unknown.push(glyph.join("\n"));
+        }
+    }
+
+    if !unknown.is_empty() {
+        return Err(Error::Message(format!(
+            "Unrecognized letters in {:?}:\n{}",
+            letters,
+            unknown.join("\n\n")
+        )));
+    }
+    Ok(letters)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pixels(s: &str) -> Grid<bool> {
+        Grid::parse(s.lines().map(String::from), |c| Ok(c == '#')).unwrap()
+    }
+
+    #[test]
+    fn small_font() {
+        let grid = pixels(
+            "\
+.........................
+.#..#.###.#....###..#...#
+.#..#..#..#....#..#.#...#
+.####..#..#....#..#..#.#.
+.#..#..#..#....###....#..
+.#..#..#..#....#......#..
+.#..#.###.####.#......#..
+.........................",
+        );
+        assert_eq!(read(&grid).unwrap(), "HILPY");
+    }
+
+    #[test]
+    fn large_font_points() {
+        let points = FONT_6X10[1].1.iter().enumerate().flat_map(|(y, row)| {
+            row.match_indices('#')
+                .map(move |(x, _)| Point::new(x as i32 - 5, y as i32 + 20))
+        });
+        assert_eq!(read_points(points).unwrap(), "B");
+    }
+
+    #[test]
+    fn unknown_letters() {
+        let grid = pixels(
+            "\
+#..#.#..#
+#..#.#..#
+####.#..#
+#..#.#..#
+#..#.#..#
+#..#.####",
+        );
+        match read(&grid) {
+            Err(e) => assert_eq!(
+                e.to_string(),
+                "Unrecognized letters in \"H?\":\n\
+                 #..#\n#..#\n#..#\n#..#\n#..#\n####"
+            ),
+            Ok(s) => panic!("expected unknown letter, got {:?}", s),
+        }
+        assert!(read(&pixels("#\n#")).is_err());
+    }
+}
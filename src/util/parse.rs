@@ -0,0 +1,192 @@
+//! Small function-based parser combinators.
+//!
+//! A parser is any `Fn(&str) -> PResult<T>`, returning the unconsumed input
+//! along with its output. Failures remember where they happened and what was
+//! expected there, which [`all`] turns into a [`ParseError`].
+
+use std::{any::type_name, borrow::Cow, cmp::Ordering, fmt, str::FromStr};
+
+use crate::util::{Error, Result};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure<'a> {
+    rest: &'a str,
+    expected: Cow<'static, str>,
+}
+
+pub type PResult<'a, T> = std::result::Result<(&'a str, T), Failure<'a>>;
+
+pub fn fail<T>(rest: &str, expected: impl Into<Cow<'static, str>>) -> PResult<'_, T> {
+    Err(Failure {
+        rest,
+        expected: expected.into(),
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Byte offset into the parsed string.
+    pub offset: usize,
+    pub expected: Cow<'static, str>,
+    pub found: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "expected {}, found {}", self.expected, self.found)
+    }
+}
+
+/// Runs the parser over all of `input`, failing on any leftover characters.
+pub fn all<'a, T>(parser: impl Fn(&'a str) -> PResult<'a, T>, input: &'a str) -> Result<T> {
+    let failure = match parser(input) {
+        Ok(("", output)) => return Ok(output),
+        Ok((rest, _)) => Failure {
+            rest,
+            expected: "end of input".into(),
+        },
+        Err(f) => f,
+    };
+
+    let found = (failure.rest.chars().next())
+        .map_or_else(|| "end of input".to_string(), |c| format!("{c:?}"));
+    Err(Error::Syntax(ParseError {
+        offset: input.len() - failure.rest.len(),
+        expected: failure.expected,
+        found,
+    }))
+}
+
+fn take_while(i: &str, f: impl Fn(char) -> bool) -> (&str, &str) {
+    let end = i.find(|c| !f(c)).unwrap_or(i.len());
+    (&i[end..], &i[..end])
+}
+
+pub fn tag<'a>(t: &'static str) -> impl Fn(&'a str) -> PResult<'a, &'a str> {
+    move |i: &'a str| {
+        let Some(rest) = i.strip_prefix(t) else {
+            return fail(i, format!("{t:?}"));
+        };
+        Ok((rest, &i[..t.len()]))
+    }
+}
+
+pub fn digits(i: &str) -> PResult<'_, &str> {
+    match take_while(i, |c| c.is_ascii_digit()) {
+        (_, "") => fail(i, "digits"),
+        r => Ok(r),
+    }
+}
+
+pub fn alphanumeric(i: &str) -> PResult<'_, &str> {
+    match take_while(i, char::is_alphanumeric) {
+        (_, "") => fail(i, "alphanumeric characters"),
+        r => Ok(r),
+    }
+}
+
+/// Parses a number with an optional leading sign. Values that don't fit in
+/// `T` fail with the name of `T` as the expected token.
+pub fn number<T: FromStr>(i: &str) -> PResult<'_, T> {
+    let sign = usize::from(i.starts_with('-') || i.starts_with('+'));
+    let (rest, _) = digits(&i[sign..]).or_else(|_| fail(i, "number"))?;
+    let Ok(n) = i[..i.len() - rest.len()].parse() else {
+        return fail(i, type_name::<T>());
+    };
+    Ok((rest, n))
+}
+
+/// Skips any spaces before running the parser.
+pub fn ws<'a, T>(p: impl Fn(&'a str) -> PResult<'a, T>) -> impl Fn(&'a str) -> PResult<'a, T> {
+    move |i: &'a str| p(i.trim_start_matches(' '))
+}
+
+pub fn map<'a, A, B>(
+    p: impl Fn(&'a str) -> PResult<'a, A>,
+    f: impl Fn(A) -> B,
+) -> impl Fn(&'a str) -> PResult<'a, B> {
+    move |i: &'a str| p(i).map(|(i, a)| (i, f(a)))
+}
+
+pub fn preceded<'a, A, B>(
+    a: impl Fn(&'a str) -> PResult<'a, A>,
+    b: impl Fn(&'a str) -> PResult<'a, B>,
+) -> impl Fn(&'a str) -> PResult<'a, B> {
+    move |i: &'a str| b(a(i)?.0)
+}
+
+pub fn delimited<'a, A, B, C>(
+    a: impl Fn(&'a str) -> PResult<'a, A>,
+    b: impl Fn(&'a str) -> PResult<'a, B>,
+    c: impl Fn(&'a str) -> PResult<'a, C>,
+) -> impl Fn(&'a str) -> PResult<'a, B> {
+    move |i: &'a str| {
+        let (i, output) = b(a(i)?.0)?;
+        Ok((c(i)?.0, output))
+    }
+}
+
+pub fn separated_pair<'a, A, S, B>(
+    a: impl Fn(&'a str) -> PResult<'a, A>,
+    sep: impl Fn(&'a str) -> PResult<'a, S>,
+    b: impl Fn(&'a str) -> PResult<'a, B>,
+) -> impl Fn(&'a str) -> PResult<'a, (A, B)> {
+    move |i: &'a str| {
+        let (i, first) = a(i)?;
+        let (i, second) = b(sep(i)?.0)?;
+        Ok((i, (first, second)))
+    }
+}
+
+/// Tries `a`, then `b`. If both fail, reports whichever got further, or both
+/// expectations if they failed at the same place.
+pub fn alt<'a, T>(
+    a: impl Fn(&'a str) -> PResult<'a, T>,
+    b: impl Fn(&'a str) -> PResult<'a, T>,
+) -> impl Fn(&'a str) -> PResult<'a, T> {
+    move |i: &'a str| {
+        let fa = match a(i) {
+            Ok(r) => return Ok(r),
+            Err(f) => f,
+        };
+        let fb = match b(i) {
+            Ok(r) => return Ok(r),
+            Err(f) => f,
+        };
+        Err(match fa.rest.len().cmp(&fb.rest.len()) {
+            Ordering::Less => fa,
+            Ordering::Greater => fb,
+            Ordering::Equal => Failure {
+                rest: fa.rest,
+                expected: format!("{} or {}", fa.expected, fb.expected).into(),
+            },
+        })
+    }
+}
+
+/// Makes the parser optional, succeeding with `None` without consuming input.
+pub fn opt<'a, T>(
+    p: impl Fn(&'a str) -> PResult<'a, T>,
+) -> impl Fn(&'a str) -> PResult<'a, Option<T>> {
+    move |i: &'a str| match p(i) {
+        Ok((i, output)) => Ok((i, Some(output))),
+        Err(_) => Ok((i, None)),
+    }
+}
+
+/// Parses one or more items separated by `sep`.
+pub fn separated_list1<'a, S, T>(
+    sep: impl Fn(&'a str) -> PResult<'a, S>,
+    p: impl Fn(&'a str) -> PResult<'a, T>,
+) -> impl Fn(&'a str) -> PResult<'a, Vec<T>> {
+    move |i: &'a str| {
+        let (mut i, first) = p(i)?;
+        let mut items = vec![first];
+        while let Ok((rest, _)) = sep(i) {
+            let (rest, item) = p(rest)?;
+            items.push(item);
+            i = rest;
+        }
+        Ok((i, items))
+    }
+}
@@ -0,0 +1,97 @@
+use std::{collections::BTreeMap, fmt, io::BufRead};
+
+use crate::util::{trace::Tracer, Answer, Error, Result, Timing};
+
+/// Object-safe view of an [`Answer`], so solvers of different types can be
+/// stored in a single table.
+pub trait Solver {
+    fn solve(&self, reader: &mut dyn BufRead) -> Result<String>;
+    fn solve_timed(&self, reader: &mut dyn BufRead) -> Result<(String, Timing)>;
+    fn solve_traced(&self, reader: &mut dyn BufRead, tracer: &mut dyn Tracer) -> Result<String>;
+}
+
+impl<A: Answer> Solver for A
+where
+    A::Output: ToString,
+{
+    fn solve(&self, reader: &mut dyn BufRead) -> Result<String> {
+        self.run(reader)
+    }
+    fn solve_timed(&self, reader: &mut dyn BufRead) -> Result<(String, Timing)> {
+        self.run_timed(reader)
+    }
+    fn solve_traced(&self, reader: &mut dyn BufRead, tracer: &mut dyn Tracer) -> Result<String> {
+        self.run_traced(reader, tracer)
+    }
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone, Debug)]
+pub struct Key {
+    pub year: i32,
+    pub day: i32,
+    pub part: i32,
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "AoC {} - Day {} - Part {}",
+            self.year, self.day, self.part
+        )
+    }
+}
+
+// Tags any error from the wrapped solver with its puzzle.
+struct Tagged<S> {
+    key: Key,
+    solver: S,
+}
+
+impl<S: Solver> Solver for Tagged<S> {
+    fn solve(&self, reader: &mut dyn BufRead) -> Result<String> {
+        self.solver
+            .solve(reader)
+            .map_err(|e| Error::Solver(self.key, Box::new(e)))
+    }
+    fn solve_timed(&self, reader: &mut dyn BufRead) -> Result<(String, Timing)> {
+        self.solver
+            .solve_timed(reader)
+            .map_err(|e| Error::Solver(self.key, Box::new(e)))
+    }
+    fn solve_traced(&self, reader: &mut dyn BufRead, tracer: &mut dyn Tracer) -> Result<String> {
+        self.solver
+            .solve_traced(reader, tracer)
+            .map_err(|e| Error::Solver(self.key, Box::new(e)))
+    }
+}
+
+#[derive(Default)]
+pub struct Registry {
+    solvers: BTreeMap<Key, Box<dyn Solver>>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, year: i32, day: i32, part: i32, solver: impl Solver + 'static) {
+        let key = Key { year, day, part };
+        let solver = Box::new(Tagged { key, solver });
+        assert!(
+            self.solvers.insert(key, solver).is_none(),
+            "{} registered twice",
+            key
+        );
+    }
+
+    pub fn get(&self, key: Key) -> Option<&dyn Solver> {
+        self.solvers.get(&key).map(|s| &**s)
+    }
+
+    // Iterates over all solvers ordered by year, then day, then part.
+    pub fn iter(&self) -> impl Iterator<Item = (Key, &dyn Solver)> {
+        self.solvers.iter().map(|(&k, s)| (k, &**s))
+    }
+}
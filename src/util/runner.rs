@@ -1,13 +1,106 @@
 use std::{
-    io::BufRead,
-    iter::*,
+    convert::Infallible,
+    io::{self, BufRead, Read},
     marker::PhantomData,
     str::{from_utf8, FromStr},
+    time::{Duration, Instant},
 };
 
 use itertools::{process_results, unfold};
 
-use crate::util::{Error, Result};
+use crate::util::{trace::Tracer, Error, Location, Result};
+
+#[derive(Copy, Clone, Debug)]
+struct Position {
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+
+/// Wraps an input reader to keep track of the current line and byte offset,
+/// so parse errors can point at the offending input.
+pub struct Reader<R> {
+    inner: R,
+    pos: Position,
+    // Bytes of the current line consumed so far
+    line: Vec<u8>,
+}
+
+fn advance(pos: &mut Position, line: &mut Vec<u8>, bytes: &[u8]) {
+    for &b in bytes {
+        pos.offset += 1;
+        if b == b'\n' {
+            pos.line += 1;
+            pos.column = 0;
+            line.clear();
+        } else {
+            pos.column += 1;
+            line.push(b);
+        }
+    }
+}
+
+impl<R: BufRead> Reader<R> {
+    pub const fn new(inner: R) -> Self {
+        Self {
+            inner,
+            pos: Position {
+                offset: 0,
+                line: 1,
+                column: 0,
+            },
+            line: Vec::new(),
+        }
+    }
+
+    // Consumes the rest of the current line and returns the whole line.
+    fn finish_line(&mut self) -> String {
+        let mut line = self.line.clone();
+        // The input is already known to be bad, so a read error here only
+        // shortens the snippet.
+        let _ = self.inner.read_until(b'\n', &mut line);
+        if line.last() == Some(&b'\n') {
+            line.pop();
+        }
+        String::from_utf8_lossy(&line).into_owned()
+    }
+}
+
+impl<R: BufRead> Read for Reader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        advance(&mut self.pos, &mut self.line, &buf[..n]);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for Reader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+    fn consume(&mut self, amt: usize) {
+        // The buffer is already filled, so this cannot fail.
+        if let Ok(buf) = self.inner.fill_buf() {
+            advance(&mut self.pos, &mut self.line, &buf[..amt]);
+        }
+        self.inner.consume(amt);
+    }
+}
+
+// Attaches the location of a bad input item starting at `start` on `text`.
+fn locate(start: Position, text: String, error: Error) -> Error {
+    let within = match &error {
+        Error::Syntax(e) => e.offset,
+        _ => 0,
+    };
+    let location = Location {
+        line: start.line,
+        column: start.column + within + 1,
+        offset: start.offset + within,
+        text,
+    };
+    Error::Located(location, Box::new(error))
+}
 
 pub struct Bytes;
 pub struct Lines;
@@ -16,21 +109,20 @@ pub struct ByWhitespace<T>(PhantomData<T>);
 
 pub trait Input: Sized {
     type Item;
-    fn next_input(reader: &mut impl BufRead) -> Result<Option<Self::Item>>;
+    fn next_input(reader: &mut Reader<impl BufRead>) -> Result<Option<Self::Item>>;
 }
 
 impl Input for () {
-    type Item = !;
-    fn next_input(_: &mut impl BufRead) -> Result<Option<Self::Item>> {
+    type Item = Infallible;
+    fn next_input(_: &mut Reader<impl BufRead>) -> Result<Option<Self::Item>> {
         Ok(None)
     }
 }
 
-#[allow(clippy::use_self)]
 impl Input for String {
-    type Item = String;
-    fn next_input(reader: &mut impl BufRead) -> Result<Option<Self::Item>> {
-        let mut buf = String::new();
+    type Item = Self;
+    fn next_input(reader: &mut Reader<impl BufRead>) -> Result<Option<Self::Item>> {
+        let mut buf = Self::new();
         Ok(match reader.read_to_string(&mut buf)? {
             0 => None,
             _ => Some(buf),
@@ -40,7 +132,7 @@ impl Input for String {
 
 impl Input for Bytes {
     type Item = u8;
-    fn next_input(reader: &mut impl BufRead) -> Result<Option<Self::Item>> {
+    fn next_input(reader: &mut Reader<impl BufRead>) -> Result<Option<Self::Item>> {
         let mut byte = [0];
         Ok(match reader.read(&mut byte)? {
             0 => None,
@@ -51,14 +143,14 @@ impl Input for Bytes {
 
 impl Input for Lines {
     type Item = String;
-    fn next_input(reader: &mut impl BufRead) -> Result<Option<Self::Item>> {
+    fn next_input(reader: &mut Reader<impl BufRead>) -> Result<Option<Self::Item>> {
         let mut line = String::new();
         if reader.read_line(&mut line)? == 0 {
             Ok(None)
         } else {
-            if line.ends_with('\n') {
-                line.pop();
-            }
+            // Some stored inputs have Windows line endings.
+            let len = line.trim_end_matches('\n').trim_end_matches('\r').len();
+            line.truncate(len);
             Ok(Some(line))
         }
     }
@@ -69,11 +161,15 @@ where
     Error: From<<T as FromStr>::Err>,
 {
     type Item = T;
-    fn next_input(reader: &mut impl BufRead) -> Result<Option<Self::Item>> {
-        Ok(match Lines::next_input(reader)? {
-            Some(line) => Some(line.parse()?),
-            None => None,
-        })
+    fn next_input(reader: &mut Reader<impl BufRead>) -> Result<Option<Self::Item>> {
+        let start = reader.pos;
+        let Some(line) = Lines::next_input(reader)? else {
+            return Ok(None);
+        };
+        match line.parse() {
+            Ok(item) => Ok(Some(item)),
+            Err(e) => Err(locate(start, line, e.into())),
+        }
     }
 }
 
@@ -120,17 +216,34 @@ where
     Error: From<<T as FromStr>::Err>,
 {
     type Item = T;
-    fn next_input(reader: &mut impl BufRead) -> Result<Option<Self::Item>> {
+    fn next_input(reader: &mut Reader<impl BufRead>) -> Result<Option<Self::Item>> {
         clear_whitespace(reader)?;
+        let start = reader.pos;
         let s = read_until_whitespace(reader)?;
         if s.is_empty() {
-            Ok(None)
-        } else {
-            Ok(Some(s.parse()?))
+            return Ok(None);
+        }
+        match s.parse() {
+            Ok(item) => Ok(Some(item)),
+            Err(e) => Err(locate(start, reader.finish_line(), e.into())),
         }
     }
 }
 
+fn read_inputs<I: Input, R: BufRead>(r: R) -> impl Iterator<Item = Result<I::Item>> {
+    unfold(Reader::new(r), |r| match I::next_input(r) {
+        Ok(Some(i)) => Some(Ok(i)),
+        Ok(None) => None,
+        Err(e) => Some(Err(e)),
+    })
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Timing {
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
 pub trait Answer {
     type Input: Input;
     type Output;
@@ -140,16 +253,74 @@ pub trait Answer {
         inputs: impl Iterator<Item = <Self::Input as Input>::Item>,
     ) -> Result<Self::Output>;
 
+    /// Like `ans()`, but showing the steps of the solution to the tracer.
+    /// Solvers with nothing to show just solve.
+    fn ans_traced(
+        &self,
+        inputs: impl Iterator<Item = <Self::Input as Input>::Item>,
+        _tracer: &mut dyn Tracer,
+    ) -> Result<Self::Output> {
+        self.ans(inputs)
+    }
+
     fn run<R: BufRead>(&self, r: R) -> Result<String>
     where
         Self::Output: ToString,
     {
-        let inputs = unfold(r, |r| match Self::Input::next_input(r) {
-            Ok(Some(i)) => Some(Ok(i)),
-            Ok(None) => None,
-            Err(e) => Some(Err(e)),
-        });
-        let answer = process_results(inputs, |inputs| self.ans(inputs))?;
+        let answer = process_results(read_inputs::<Self::Input, _>(r), |inputs| self.ans(inputs))?;
+        Ok(answer?.to_string())
+    }
+
+    fn run_traced<R: BufRead>(&self, r: R, tracer: &mut dyn Tracer) -> Result<String>
+    where
+        Self::Output: ToString,
+    {
+        let answer = process_results(read_inputs::<Self::Input, _>(r), |inputs| {
+            self.ans_traced(inputs, tracer)
+        })?;
         Ok(answer?.to_string())
     }
+
+    // Unlike run(), all input is read before solving so the two phases can
+    // be timed separately.
+    fn run_timed<R: BufRead>(&self, r: R) -> Result<(String, Timing)>
+    where
+        Self::Output: ToString,
+    {
+        let start = Instant::now();
+        let inputs: Vec<_> = read_inputs::<Self::Input, _>(r).collect::<Result<_>>()?;
+        let parse = start.elapsed();
+
+        let start = Instant::now();
+        let answer = self.ans(inputs.into_iter())?.to_string();
+        let solve = start.elapsed();
+        Ok((answer, Timing { parse, solve }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn location<I: Input>(input: &str) -> Location {
+        match read_inputs::<I, _>(input.as_bytes()).find_map(Result::err) {
+            Some(Error::Located(l, _)) => l,
+            r => panic!("expected located error, got {:?}", r),
+        }
+    }
+
+    #[test]
+    fn by_line_location() {
+        let l = location::<ByLine<i32>>("1\n-2\n+x3\n4\n");
+        assert_eq!((l.line, l.column, l.offset), (3, 1, 5));
+        assert_eq!(l.text, "+x3");
+    }
+
+    #[test]
+    fn by_whitespace_location() {
+        let l = location::<ByWhitespace<u8>>("1 2\n 3  x5 6\n7");
+        assert_eq!((l.line, l.column, l.offset), (2, 5, 8));
+        assert_eq!(l.text, " 3  x5 6");
+        assert_eq!(l.to_string(), "2 |  3  x5 6\n  |     ^");
+    }
 }
@@ -0,0 +1,254 @@
+//! Ordering and scheduling jobs with dependencies between them.
+//!
+//! Jobs are given as `(before, after)` edges. Whenever several jobs are ready
+//! the smallest one goes first.
+
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, HashSet},
+    fmt,
+    hash::Hash,
+};
+
+use itertools::Itertools;
+
+use crate::util::{Error, Result};
+
+struct Scheduler<J> {
+    depends_on: HashMap<J, Vec<J>>,
+    remaining_deps: HashMap<J, usize>,
+    ready: BinaryHeap<Reverse<J>>,
+}
+
+impl<J: Clone + Ord + Hash> Scheduler<J> {
+    fn new(edges: impl IntoIterator<Item = (J, J)>) -> Self {
+        let edges: HashSet<_> = edges.into_iter().collect();
+        let mut depends_on = HashMap::<J, Vec<J>>::new();
+        let mut remaining_deps = HashMap::<J, usize>::new();
+        for (first, next) in edges {
+            depends_on.entry(next.clone()).or_default();
+            *remaining_deps.entry(next.clone()).or_default() += 1;
+            depends_on.entry(first).or_default().push(next);
+        }
+
+        let ready = depends_on
+            .keys()
+            .filter(|j| !remaining_deps.contains_key(j))
+            .map(|j| Reverse(j.clone()))
+            .collect();
+
+        Self {
+            depends_on,
+            remaining_deps,
+            ready,
+        }
+    }
+
+    fn complete(&mut self, j: &J) {
+        for job in self.depends_on.remove(j).unwrap_or_default() {
+            let Entry::Occupied(mut e) = self.remaining_deps.entry(job) else {
+                unreachable!()
+            };
+            let count = e.get_mut();
+            *count -= 1;
+            if *count == 0 {
+                let (job, _) = e.remove_entry();
+                self.ready.push(Reverse(job));
+            }
+        }
+    }
+
+    fn next_job(&mut self) -> Option<J> {
+        Some(self.ready.pop()?.0)
+    }
+
+    // Called once no more jobs are ready. Any job still waiting on others is
+    // (directly or indirectly) blocked by a cycle, so walk back along unmet
+    // dependencies until a job repeats.
+    fn finish(self) -> Result<()>
+    where
+        J: fmt::Debug,
+    {
+        let Some(mut job) = self.remaining_deps.keys().min().cloned() else {
+            return Ok(());
+        };
+        let mut path = Vec::new();
+        while !path.contains(&job) {
+            path.push(job.clone());
+            job = self
+                .depends_on
+                .iter()
+                .filter(|(first, _)| self.remaining_deps.contains_key(*first))
+                .find(|(_, nexts)| nexts.contains(&job))
+                .map(|(first, _)| first.clone())
+                .expect("blocked jobs have a blocked dependency");
+        }
+        let start = path.iter().position(|j| *j == job).unwrap();
+        let mut cycle = path.split_off(start);
+        cycle.sort();
+        Err(Error::Message(format!(
+            "Dependency cycle between {:?}",
+            cycle.iter().format(", ")
+        )))
+    }
+}
+
+/// Returns every job in dependency order.
+pub fn order<J>(edges: impl IntoIterator<Item = (J, J)>) -> Result<Vec<J>>
+where
+    J: Clone + Ord + Hash + fmt::Debug,
+{
+    let mut s = Scheduler::new(edges);
+    let mut order = Vec::new();
+    while let Some(job) = s.next_job() {
+        s.complete(&job);
+        order.push(job);
+    }
+    s.finish()?;
+    Ok(order)
+}
+
+/// A job run by one worker from `start` until just before `end`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Run<J> {
+    pub worker: usize,
+    pub job: J,
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct Timeline<J> {
+    pub workers: usize,
+    pub runs: Vec<Run<J>>,
+    /// Time at which the last job finishes.
+    pub total: usize,
+}
+
+impl<J> Timeline<J> {
+    /// What each worker is doing at every tick.
+    pub fn ticks(&self) -> impl Iterator<Item = Vec<Option<&J>>> {
+        (0..self.total).map(move |t| {
+            let mut workers = vec![None; self.workers];
+            for run in &self.runs {
+                if (run.start..run.end).contains(&t) {
+                    workers[run.worker] = Some(&run.job);
+                }
+            }
+            workers
+        })
+    }
+}
+
+// One line per tick, with a column per worker.
+impl<J: fmt::Display> fmt::Display for Timeline<J> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (t, workers) in self.ticks().enumerate() {
+            write!(f, "{t:>6}")?;
+            for job in workers {
+                match job {
+                    Some(job) => write!(f, " {job:>4}")?,
+                    None => write!(f, " {:>4}", '.')?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Simulates `workers` workers each taking the next ready job as soon as they
+/// are free, where running a job takes `cost(job)` ticks.
+pub fn simulate<J>(
+    edges: impl IntoIterator<Item = (J, J)>,
+    workers: usize,
+    cost: impl Fn(&J) -> usize,
+) -> Result<Timeline<J>>
+where
+    J: Clone + Ord + Hash + fmt::Debug,
+{
+    if workers == 0 {
+        return Err(Error::Custom("At least one worker is required"));
+    }
+    let mut s = Scheduler::new(edges);
+    let mut runs: Vec<Run<J>> = Vec::new();
+    // Indices into runs for each worker's current job
+    let mut running: Vec<Option<usize>> = vec![None; workers];
+    let mut now = 0;
+
+    loop {
+        // Finish jobs, then hand out whatever became ready
+        for slot in &mut running {
+            if let Some(i) = *slot {
+                if runs[i].end == now {
+                    s.complete(&runs[i].job);
+                    *slot = None;
+                }
+            }
+        }
+        for (worker, slot) in running.iter_mut().enumerate() {
+            if slot.is_some() {
+                continue;
+            }
+            let Some(job) = s.next_job() else {
+                break;
+            };
+            let end = now + cost(&job);
+            *slot = Some(runs.len());
+            runs.push(Run {
+                worker,
+                job,
+                start: now,
+                end,
+            });
+        }
+
+        match running.iter().filter_map(|&i| Some(runs[i?].end)).min() {
+            Some(end) => now = end,
+            None => break,
+        }
+    }
+    s.finish()?;
+    Ok(Timeline {
+        workers,
+        runs,
+        total: now,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lexicographic_order() {
+        let edges = vec![('b', 'a'), ('c', 'a'), ('c', 'b'), ('d', 'e')];
+        assert_eq!(order(edges).unwrap(), ['c', 'b', 'a', 'd', 'e']);
+    }
+
+    #[test]
+    fn reports_cycle() {
+        let edges = vec![('a', 'b'), ('b', 'c'), ('c', 'd'), ('d', 'b'), ('d', 'e')];
+        match order(edges) {
+            Err(e) => assert_eq!(e.to_string(), "Dependency cycle between 'b', 'c', 'd'"),
+            Ok(o) => panic!("expected cycle, got {:?}", o),
+        }
+    }
+
+    #[test]
+    fn timeline() {
+        let edges = vec![("a", "c"), ("b", "c")];
+        let t = simulate(edges, 2, |j| if *j == "a" { 1 } else { 2 }).unwrap();
+        assert_eq!(t.total, 4);
+        let ticks: Vec<_> = t.ticks().collect();
+        assert_eq!(
+            ticks,
+            [
+                [Some(&"a"), Some(&"b")],
+                [None, Some(&"b")],
+                [Some(&"c"), None],
+                [Some(&"c"), None],
+            ]
+        );
+    }
+}
@@ -0,0 +1,48 @@
+//! Summed-area tables, for constant time sums over rectangles of a grid.
+
+use std::ops::{Add, Range, Sub};
+
+use crate::util::grid::{Grid, Point};
+
+pub struct SummedArea<T> {
+    // sums[(x, y)] is the sum of all cells above and to the left of (x, y),
+    // so the table has an extra row and column of zeros.
+    sums: Grid<T>,
+}
+
+impl<T> SummedArea<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    pub fn new(grid: &Grid<T>) -> Self {
+        let mut sums = Grid::filled(grid.width() + 1, grid.height() + 1, T::default());
+        for (p, &value) in grid.iter() {
+            let (x, y) = (p.x + 1, p.y + 1);
+            sums[Point::new(x, y)] =
+                value + sums[Point::new(x - 1, y)] + sums[Point::new(x, y - 1)]
+                    - sums[Point::new(x - 1, y - 1)];
+        }
+        Self { sums }
+    }
+
+    /// Sum of the cells with coordinates in the given ranges.
+    pub fn sum(&self, x: Range<usize>, y: Range<usize>) -> T {
+        let at = |x: usize, y: usize| self.sums[Point::new(x as i32, y as i32)];
+        at(x.end, y.end) + at(x.start, y.start) - at(x.start, y.end) - at(x.end, y.start)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rectangle_sums() {
+        let grid = Grid::from_fn(4, 3, |p| p.x + 10 * p.y);
+        let table = SummedArea::new(&grid);
+        assert_eq!(table.sum(0..4, 0..3), 18 + 120);
+        assert_eq!(table.sum(1..3, 1..2), 11 + 12);
+        assert_eq!(table.sum(2..2, 0..3), 0);
+        assert_eq!(table.sum(3..4, 2..3), 23);
+    }
+}
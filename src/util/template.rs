@@ -3,44 +3,54 @@ use std::{
         hash_map::{Entry::*, HashMap},
     },  
     hash::Hash,
+    str::FromStr,
 };
 
-use nom::*;
-
-use crate::util::{to_result, Answer, Parse, Result};
+use crate::util::{
+    parse::{self, alphanumeric, delimited, preceded, tag, PResult},
+    Answer, ByLine, Error, Result,
+};
 
 pub struct DaThing<J> {
     first: J,
     next: J,
 }
 
-named!(da_thing<&str,DaThing<String>>, do_parse!(
-    tag!("Step ") >> first: map!(alphanumeric, String::from) >>
-    tag!(" must be finished before step ") >>
-    next: map!(alphanumeric, String::from) >> tag!(" can begin.") >>
-    (DaThing{first, next})
-));
+fn da_thing(i: &str) -> PResult<'_, DaThing<String>> {
+    let (i, first) = preceded(tag("Step "), alphanumeric)(i)?;
+    let (i, next) = delimited(
+        tag(" must be finished before step "),
+        alphanumeric,
+        tag(" can begin."),
+    )(i)?;
+    let thing = DaThing {
+        first: first.to_string(),
+        next: next.to_string(),
+    };
+    Ok((i, thing))
+}
 
-impl Parse for DaThing<String> {
-    fn parse(s: &str) -> Result<Self> {
-        to_result(da_thing(s))
+impl FromStr for DaThing<String> {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        parse::all(da_thing, s)
     }
 }
 
 pub struct Day1;
 impl Answer for Day1 {
-    type Input = DaThing<String>;
+    type Input = ByLine<DaThing<String>>;
     type Output = String;
-    fn ans(&self, inputs: impl Iterator<Item = Self::Input>) -> Result<Self::Output> {
+    fn ans(&self, inputs: impl Iterator<Item = DaThing<String>>) -> Result<Self::Output> {
         unimplemented!()
     }
 }
 
 pub struct Day2;
 impl Answer for Day2 {
-    type Input = DaThing<String>;
+    type Input = ByLine<DaThing<String>>;
     type Output = usize;
-    fn ans(&self, inputs: impl Iterator<Item = Self::Input>) -> Result<Self::Output> {
+    fn ans(&self, inputs: impl Iterator<Item = DaThing<String>>) -> Result<Self::Output> {
         unimplemented!()
     }
 }
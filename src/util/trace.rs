@@ -0,0 +1,90 @@
+//! Watching simulations as they run.
+//!
+//! Solvers that have something to show pass each state to a [`Tracer`] from
+//! [`Answer::ans_traced`](crate::util::Answer::ans_traced) as a frame.
+
+use std::{
+    fmt, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+
+use crate::util::{Error, Result};
+
+pub trait Tracer {
+    /// Shows one state of the simulation, with a label saying which. Both are
+    /// only formatted if the frame is shown.
+    fn frame(&mut self, label: fmt::Arguments<'_>, frame: &dyn fmt::Display);
+}
+
+/// Ignores every frame.
+pub struct Quiet;
+
+impl Tracer for Quiet {
+    fn frame(&mut self, _: fmt::Arguments<'_>, _: &dyn fmt::Display) {}
+}
+
+/// Draws each frame over the last one on stderr, pausing after each so they
+/// can be followed.
+pub struct Terminal {
+    pub delay: Duration,
+}
+
+impl Tracer for Terminal {
+    fn frame(&mut self, label: fmt::Arguments<'_>, frame: &dyn fmt::Display) {
+        // Move to the top left, then clear the screen.
+        eprint!("\x1b[H\x1b[J{label}\n{frame}\n");
+        thread::sleep(self.delay);
+    }
+}
+
+/// Writes each frame to its own numbered text file in a directory.
+pub struct Frames {
+    dir: PathBuf,
+    count: usize,
+    // Later frames are skipped after a failed write.
+    error: Option<(PathBuf, io::Error)>,
+}
+
+impl Frames {
+    pub fn new(dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        if let Err(e) = fs::create_dir_all(&dir) {
+            return Err(Error::Output(dir, e));
+        }
+        Ok(Self {
+            dir,
+            count: 0,
+            error: None,
+        })
+    }
+
+    /// Returns the number of frames written, or the first write error.
+    pub fn finish(self) -> Result<usize> {
+        match self.error {
+            Some((path, e)) => Err(Error::Output(path, e)),
+            None => Ok(self.count),
+        }
+    }
+}
+
+fn write_frame(path: &Path, label: fmt::Arguments<'_>, frame: &dyn fmt::Display) -> io::Result<()> {
+    let mut file = io::BufWriter::new(fs::File::create(path)?);
+    writeln!(file, "{label}\n{frame}")?;
+    file.flush()
+}
+
+impl Tracer for Frames {
+    fn frame(&mut self, label: fmt::Arguments<'_>, frame: &dyn fmt::Display) {
+        if self.error.is_some() {
+            return;
+        }
+        let path = self.dir.join(format!("{:06}.txt", self.count));
+        match write_frame(&path, label, frame) {
+            Ok(()) => self.count += 1,
+            Err(e) => self.error = Some((path, e)),
+        }
+    }
+}
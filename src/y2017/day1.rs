@@ -0,0 +1,69 @@
+use crate::examples;
+use crate::util::{Answer, Bytes, Error, Result};
+
+const NO_DIGITS: Error = Error::Custom("No digits in input");
+
+fn digits(inputs: impl Iterator<Item = u8>) -> Result<Vec<u32>> {
+    inputs
+        .filter(|b| !b.is_ascii_whitespace())
+        .map(|b| {
+            let c = char::from(b);
+            c.to_digit(10)
+                .ok_or_else(|| Error::Parse(format!("Found non-digit {c:?} in input")))
+        })
+        .collect()
+}
+
+// Sums the digits matching the digit `offset` places further along the
+// circular list.
+fn sum_matching(digits: &[u32], offset: usize) -> u32 {
+    let n = digits.len();
+    (0..n)
+        .filter(|&i| digits[i] == digits[(i + offset) % n])
+        .map(|i| digits[i])
+        .sum()
+}
+
+pub struct Sequential;
+impl Answer for Sequential {
+    type Input = Bytes;
+    type Output = u32;
+    fn ans(&self, inputs: impl Iterator<Item = u8>) -> Result<Self::Output> {
+        let digits = digits(inputs)?;
+        if digits.is_empty() {
+            return Err(NO_DIGITS);
+        }
+        Ok(sum_matching(&digits, 1))
+    }
+}
+
+pub struct Halfway;
+impl Answer for Halfway {
+    type Input = Bytes;
+    type Output = u32;
+    fn ans(&self, inputs: impl Iterator<Item = u8>) -> Result<Self::Output> {
+        let digits = digits(inputs)?;
+        if digits.is_empty() {
+            return Err(NO_DIGITS);
+        }
+        if digits.len() % 2 != 0 {
+            return Err(Error::Parse(format!(
+                "Input length of {} is not even",
+                digits.len()
+            )));
+        }
+        Ok(sum_matching(&digits, digits.len() / 2))
+    }
+}
+
+examples! {
+    sequential_pairs: Sequential, "1122" => 3;
+    sequential_all: Sequential, "1111" => 4;
+    sequential_none: Sequential, "1234" => 0;
+    sequential_wrap: Sequential, "91212129" => 9;
+    halfway_all: Halfway, "1212" => 6;
+    halfway_none: Halfway, "1221" => 0;
+    halfway_one: Halfway, "123425" => 4;
+    halfway_repeat: Halfway, "123123" => 12;
+    halfway_ones: Halfway, "12131415" => 4;
+}
@@ -0,0 +1,59 @@
+use std::str::FromStr;
+
+use itertools::{Itertools, MinMaxResult};
+
+use crate::examples;
+use crate::util::{Answer, ByLine, Error, IterExtra, Result};
+
+pub struct Row(Vec<i32>);
+
+impl FromStr for Row {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        s.split_whitespace()
+            .map(|n| Ok(n.parse()?))
+            .collect::<Result<_>>()
+            .map(Row)
+    }
+}
+
+fn checksum(rows: impl Iterator<Item = Row>, f: impl Fn(&[i32]) -> Result<i32>) -> Result<i32> {
+    rows.map(|row| f(&row.0)).sum()
+}
+
+pub struct MinMaxDiff;
+impl Answer for MinMaxDiff {
+    type Input = ByLine<Row>;
+    type Output = i32;
+    fn ans(&self, inputs: impl Iterator<Item = Row>) -> Result<Self::Output> {
+        checksum(inputs, |row| match row.iter().minmax() {
+            MinMaxResult::NoElements => Err(Error::Custom("Row has no numbers")),
+            MinMaxResult::OneElement(_) => Ok(0),
+            MinMaxResult::MinMax(min, max) => Ok(max - min),
+        })
+    }
+}
+
+pub struct EvenDiv;
+impl Answer for EvenDiv {
+    type Input = ByLine<Row>;
+    type Output = i32;
+    fn ans(&self, inputs: impl Iterator<Item = Row>) -> Result<Self::Output> {
+        checksum(inputs, |row| {
+            row.iter()
+                .tuple_combinations()
+                .filter_map(|(&a, &b)| match (a, b) {
+                    (_, 0) | (0, _) => None,
+                    _ if a % b == 0 => Some(a / b),
+                    _ if b % a == 0 => Some(b / a),
+                    _ => None,
+                })
+                .single()
+        })
+    }
+}
+
+examples! {
+    min_max_diff: MinMaxDiff, "5 1 9 5\n7 5 3\n2 4 6 8" => 18;
+    even_div: EvenDiv, "5 9 2 8\n9 4 7 3\n3 8 6 5" => 9;
+}
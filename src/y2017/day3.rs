@@ -0,0 +1,108 @@
+use std::cmp::max;
+
+use crate::examples;
+use crate::util::{Answer, ByWhitespace, Error, Result};
+
+const NO_INPUT: Error = Error::Custom("No input");
+const NOT_SQUARE: Error = Error::Custom("Squares are numbered from 1");
+
+// Squares are numbered from 1 at the origin, spiralling out anticlockwise
+// starting to the right.
+fn to_point(square: i32) -> (i32, i32) {
+    if square == 1 {
+        return (0, 0);
+    }
+    // r is the ring containing square:        (2r-1)^2 < square <= (2r+1)^2
+    let r = ((square - 1).isqrt() + 1) / 2;
+    // c is the 1-indexed count in ring r:     c ∈ (0, 8r]
+    let c = square - (2 * r - 1).pow(2);
+    match (c - 1) / (2 * r) {
+        0 => (r, c - r),      // right:        c ∈ (0 , 2r]
+        1 => (3 * r - c, r),  // top:          c ∈ (2r, 4r]
+        2 => (-r, 5 * r - c), // left:         c ∈ (4r, 6r]
+        3 => (c - 7 * r, -r), // bottom:       c ∈ (6r, 8r]
+        _ => unreachable!(),
+    }
+}
+
+fn to_square(x: i32, y: i32) -> i32 {
+    // r is the ring containing (x,y):         x or y should be ±r
+    let r = max(x.abs(), y.abs());
+    // c is the 1-indexed count in ring r:     c ∈ (0, 8r]
+    let c = if y == r {
+        3 * r - x // top:     c ∈ [2r, 4r]
+    } else if y == -r {
+        7 * r + x // bottom:  c ∈ [6r, 8r]
+    } else if x == r {
+        r + y // right:   c ∈ (0 , 2r)
+    } else {
+        5 * r - y // left:    c ∈ (4r, 6r)
+    };
+    c + (2 * r - 1).pow(2)
+}
+
+pub struct CountSteps;
+impl Answer for CountSteps {
+    type Input = ByWhitespace<i32>;
+    type Output = i32;
+    fn ans(&self, mut inputs: impl Iterator<Item = i32>) -> Result<Self::Output> {
+        let square = inputs.next().ok_or(NO_INPUT)?;
+        if square < 1 {
+            return Err(NOT_SQUARE);
+        }
+        let (x, y) = to_point(square);
+        Ok(x.abs() + y.abs())
+    }
+}
+
+// Values written to each square in order, each the sum of its already
+// written neighbors.
+#[derive(Default)]
+struct Spiral {
+    written: Vec<u64>,
+}
+
+impl Iterator for Spiral {
+    type Item = u64;
+    fn next(&mut self) -> Option<u64> {
+        let value = if self.written.is_empty() {
+            1
+        } else {
+            let (a, b) = to_point(self.written.len() as i32 + 1);
+            let mut sum = 0;
+            for x in (a - 1)..=(a + 1) {
+                for y in (b - 1)..=(b + 1) {
+                    let square = to_square(x, y) as usize;
+                    if square <= self.written.len() {
+                        sum += self.written[square - 1];
+                    }
+                }
+            }
+            sum
+        };
+        self.written.push(value);
+        Some(value)
+    }
+}
+
+pub struct FirstValue;
+impl Answer for FirstValue {
+    type Input = ByWhitespace<u64>;
+    type Output = u64;
+    fn ans(&self, mut inputs: impl Iterator<Item = u64>) -> Result<Self::Output> {
+        let input = inputs.next().ok_or(NO_INPUT)?;
+        Ok(Spiral::default()
+            .find(|&v| v > input)
+            .expect("spiral should be infinite"))
+    }
+}
+
+examples! {
+    steps_origin: CountSteps, "1" => 0;
+    steps_12: CountSteps, "12" => 3;
+    steps_23: CountSteps, "23" => 2;
+    steps_1024: CountSteps, "1024" => 31;
+    first_value_1: FirstValue, "1" => 2;
+    first_value_5: FirstValue, "5" => 10;
+    first_value_747: FirstValue, "747" => 806;
+}
@@ -0,0 +1,44 @@
+use std::{collections::HashSet, hash::Hash};
+
+use crate::examples;
+use crate::util::{Answer, Lines, Result};
+
+fn is_unique<T: Hash + Eq>(mut items: impl Iterator<Item = T>) -> bool {
+    let mut set = HashSet::new();
+    items.all(|item| set.insert(item))
+}
+
+pub struct NoDuplicates;
+impl Answer for NoDuplicates {
+    type Input = Lines;
+    type Output = usize;
+    fn ans(&self, inputs: impl Iterator<Item = String>) -> Result<Self::Output> {
+        Ok(inputs
+            .filter(|line| is_unique(line.split_whitespace()))
+            .count())
+    }
+}
+
+pub struct NoAnagrams;
+impl Answer for NoAnagrams {
+    type Input = Lines;
+    type Output = usize;
+    fn ans(&self, inputs: impl Iterator<Item = String>) -> Result<Self::Output> {
+        Ok(inputs
+            .filter(|line| {
+                is_unique(line.split_whitespace().map(|word| {
+                    let mut bytes = word.as_bytes().to_vec();
+                    bytes.sort_unstable();
+                    bytes
+                }))
+            })
+            .count())
+    }
+}
+
+examples! {
+    no_duplicates: NoDuplicates, "aa bb cc dd ee\naa bb cc dd aa\naa bb cc dd aaa" => 2;
+    no_anagrams: NoAnagrams,
+        "abcde fghij\nabcde xyz ecdab\na ab abc abd abf abj\niiii oiii ooii oooi oooo\noiii ioii iioi iiio"
+        => 3;
+}
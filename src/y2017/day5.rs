@@ -0,0 +1,41 @@
+use std::convert::TryFrom;
+
+use crate::examples;
+use crate::util::{Answer, ByLine, Result};
+
+pub const fn increment(offset: i64) -> i64 {
+    offset + 1
+}
+
+pub const fn converge(offset: i64) -> i64 {
+    if offset < 3 {
+        offset + 1
+    } else {
+        offset - 1
+    }
+}
+
+/// Counts the jumps needed to leave the list, changing each offset with the
+/// given function after it is used.
+pub struct Adjust(pub fn(i64) -> i64);
+impl Answer for Adjust {
+    type Input = ByLine<i64>;
+    type Output = usize;
+    fn ans(&self, inputs: impl Iterator<Item = i64>) -> Result<Self::Output> {
+        let mut list: Vec<_> = inputs.collect();
+
+        let mut jumps = 0;
+        let mut index = 0;
+        while let Some(jump) = usize::try_from(index).ok().and_then(|i| list.get_mut(i)) {
+            index += *jump;
+            *jump = self.0(*jump);
+            jumps += 1;
+        }
+        Ok(jumps)
+    }
+}
+
+examples! {
+    adjust_increment: Adjust(increment), "0\n3\n0\n1\n-3" => 5;
+    adjust_converge: Adjust(converge), "0\n3\n0\n1\n-3" => 10;
+}
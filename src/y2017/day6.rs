@@ -0,0 +1,67 @@
+use crate::examples;
+use crate::util::{
+    cycle::{self, Cycle},
+    Answer, ByWhitespace, Error, Result,
+};
+
+const NO_BANKS: Error = Error::Custom("No memory banks in input");
+
+fn banks(inputs: impl Iterator<Item = usize>) -> Result<Vec<usize>> {
+    let banks: Vec<_> = inputs.collect();
+    if banks.is_empty() {
+        return Err(NO_BANKS);
+    }
+    Ok(banks)
+}
+
+fn reallocate(banks: &mut [usize]) {
+    let mut max = 0;
+    let mut max_idx = 0;
+    for (idx, &bank) in banks.iter().enumerate() {
+        if bank > max {
+            max = bank;
+            max_idx = idx;
+        }
+    }
+    banks[max_idx] = 0;
+
+    let num = banks.len();
+    for i in 1..=max % num {
+        banks[(i + max_idx) % num] += 1;
+    }
+    for bank in banks {
+        *bank += max / num;
+    }
+}
+
+fn cycle(banks: Vec<usize>) -> Cycle {
+    cycle::brent(banks, |banks| {
+        let mut banks = banks.clone();
+        reallocate(&mut banks);
+        banks
+    })
+}
+
+pub struct RepeatIdx;
+impl Answer for RepeatIdx {
+    type Input = ByWhitespace<usize>;
+    type Output = usize;
+    fn ans(&self, inputs: impl Iterator<Item = usize>) -> Result<Self::Output> {
+        let cycle = cycle(banks(inputs)?);
+        Ok(cycle.start + cycle.len)
+    }
+}
+
+pub struct CycleCount;
+impl Answer for CycleCount {
+    type Input = ByWhitespace<usize>;
+    type Output = usize;
+    fn ans(&self, inputs: impl Iterator<Item = usize>) -> Result<Self::Output> {
+        Ok(cycle(banks(inputs)?).len)
+    }
+}
+
+examples! {
+    repeat_idx: RepeatIdx, "0 2 7 0" => 5;
+    cycle_count: CycleCount, "0 2 7 0" => 4;
+}
@@ -0,0 +1,163 @@
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
+
+use crate::examples;
+use crate::util::{
+    parse::{self, alphanumeric, delimited, number, opt, preceded, separated_list1, tag, PResult},
+    Answer, ByLine, Error, IterExtra, Result,
+};
+
+pub struct Program {
+    name: String,
+    weight: i64,
+    children: Vec<String>,
+}
+
+fn program(i: &str) -> PResult<'_, Program> {
+    let (i, name) = alphanumeric(i)?;
+    let (i, weight) = delimited(tag(" ("), number, tag(")"))(i)?;
+    let children = preceded(tag(" -> "), separated_list1(tag(", "), alphanumeric));
+    let (i, children) = opt(children)(i)?;
+    let program = Program {
+        name: name.to_string(),
+        weight,
+        children: children
+            .unwrap_or_default()
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+    };
+    Ok((i, program))
+}
+
+impl FromStr for Program {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        parse::all(program, s)
+    }
+}
+
+// Finds the bottom program, checking that every other program is held by
+// exactly one parent.
+fn find_root(programs: &HashMap<String, Program>) -> Result<&str> {
+    let mut names: HashSet<&str> = programs.keys().map(String::as_str).collect();
+    for program in programs.values() {
+        for child in &program.children {
+            if !names.remove(child.as_str()) {
+                return Err(Error::Message(format!(
+                    "Unknown or duplicate child {child:?}"
+                )));
+            }
+        }
+    }
+    names
+        .into_iter()
+        .single()
+        .map_err(|_| Error::Custom("Programs do not form a single tower"))
+}
+
+fn tower(inputs: impl Iterator<Item = Program>) -> Result<HashMap<String, Program>> {
+    let mut programs = HashMap::new();
+    for program in inputs {
+        if let Some(p) = programs.insert(program.name.clone(), program) {
+            return Err(Error::Message(format!("Duplicate program {:?}", p.name)));
+        }
+    }
+    Ok(programs)
+}
+
+pub struct RootName;
+impl Answer for RootName {
+    type Input = ByLine<Program>;
+    type Output = String;
+    fn ans(&self, inputs: impl Iterator<Item = Program>) -> Result<Self::Output> {
+        let programs = tower(inputs)?;
+        Ok(find_root(&programs)?.to_string())
+    }
+}
+
+struct Tree {
+    name: String,
+    weight: i64,
+    children: Vec<Self>,
+}
+
+impl Tree {
+    fn new(name: &str, programs: &mut HashMap<String, Program>) -> Result<Self> {
+        let Program {
+            name,
+            weight,
+            children,
+        } = programs
+            .remove(name)
+            .ok_or_else(|| Error::Message(format!("Cycle detected containing {name:?}")))?;
+        let children = children
+            .iter()
+            .map(|child| Self::new(child, programs))
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            name,
+            weight,
+            children,
+        })
+    }
+}
+
+enum Balance {
+    // The tree is balanced with this total weight.
+    Total(i64),
+    // A program somewhere in the tree needs to have this weight.
+    Change(i64),
+}
+
+fn balance(tree: &Tree) -> Result<Balance> {
+    let mut totals = Vec::new();
+    for child in &tree.children {
+        match balance(child)? {
+            Balance::Total(t) => totals.push(t),
+            Balance::Change(w) => return Ok(Balance::Change(w)),
+        }
+    }
+
+    let freqs = totals.iter().frequencies();
+    let (odd, even) = match freqs.len() {
+        0 | 1 => return Ok(Balance::Total(tree.weight + totals.iter().sum::<i64>())),
+        2 => {
+            let mut freqs: Vec<_> = freqs.into_iter().collect();
+            freqs.sort_by_key(|&(_, n)| n);
+            match (freqs[0], freqs[1]) {
+                ((odd, 1), (even, n)) if n > 1 => (*odd, *even),
+                _ => return Err(Error::Custom("Weight to change is ambiguous")),
+            }
+        }
+        _ => {
+            return Err(Error::Message(format!(
+                "Programs on {:?} have more than two weights",
+                tree.name
+            )))
+        }
+    };
+    let idx = totals.iter().position(|&t| t == odd).unwrap();
+    Ok(Balance::Change(tree.children[idx].weight + even - odd))
+}
+
+pub struct CorrectWeight;
+impl Answer for CorrectWeight {
+    type Input = ByLine<Program>;
+    type Output = i64;
+    fn ans(&self, inputs: impl Iterator<Item = Program>) -> Result<Self::Output> {
+        let mut programs = tower(inputs)?;
+        let root = find_root(&programs)?.to_string();
+        match balance(&Tree::new(&root, &mut programs)?)? {
+            Balance::Total(_) => Err(Error::Custom("Entire tower is balanced")),
+            Balance::Change(weight) => Ok(weight),
+        }
+    }
+}
+
+examples! {
+    root_name: RootName, include_str!("../../input/2017/day7_test.txt") => "tknk";
+    correct_weight: CorrectWeight, include_str!("../../input/2017/day7_test.txt") => 60;
+}
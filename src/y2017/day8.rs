@@ -0,0 +1,107 @@
+use std::{cmp::Ordering, collections::HashMap, str::FromStr};
+
+use crate::examples;
+use crate::util::{
+    parse::{self, alphanumeric, alt, fail, map, number, preceded, tag, ws, PResult},
+    Answer, ByLine, Error, Result,
+};
+
+type Op = fn(Ordering) -> bool;
+
+pub struct Instruction {
+    incr_reg: String,
+    incr: i32,
+    test_reg: String,
+    op: Op,
+    test: i32,
+}
+
+// Longer operators come first so "<=" isn't read as "<".
+const OPS: [(&str, Op); 6] = [
+    ("==", Ordering::is_eq),
+    ("!=", Ordering::is_ne),
+    ("<=", Ordering::is_le),
+    (">=", Ordering::is_ge),
+    ("<", Ordering::is_lt),
+    (">", Ordering::is_gt),
+];
+
+fn comparison(i: &str) -> PResult<'_, Op> {
+    for (token, op) in OPS {
+        if let Some(rest) = i.strip_prefix(token) {
+            return Ok((rest, op));
+        }
+    }
+    fail(i, "comparison operator")
+}
+
+fn instruction(i: &str) -> PResult<'_, Instruction> {
+    let (i, incr_reg) = alphanumeric(i)?;
+    let (i, sign) = ws(alt(map(tag("inc"), |_| 1), map(tag("dec"), |_| -1)))(i)?;
+    let (i, amount) = ws(number::<i32>)(i)?;
+    let (i, test_reg) = preceded(ws(tag("if")), ws(alphanumeric))(i)?;
+    let (i, op) = ws(comparison)(i)?;
+    let (i, test) = ws(number)(i)?;
+    let instruction = Instruction {
+        incr_reg: incr_reg.to_string(),
+        incr: sign * amount,
+        test_reg: test_reg.to_string(),
+        op,
+        test,
+    };
+    Ok((i, instruction))
+}
+
+impl FromStr for Instruction {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        parse::all(instruction, s)
+    }
+}
+
+// Returns the largest register value at the end, and at any point during,
+// running the instructions.
+fn largest_values(inputs: impl Iterator<Item = Instruction>) -> Result<(i32, i32)> {
+    let mut registers = HashMap::new();
+    let max_during = inputs
+        .filter_map(|i| {
+            let test_val: i32 = *registers.entry(i.test_reg).or_default();
+            if (i.op)(test_val.cmp(&i.test)) {
+                let incr_val = registers.entry(i.incr_reg).or_default();
+                *incr_val += i.incr;
+                Some(*incr_val)
+            } else {
+                None
+            }
+        })
+        .max()
+        .ok_or(Error::Custom("No registers incremented"))?;
+    let max_final = *registers
+        .values()
+        .max()
+        .ok_or(Error::Custom("No registers used"))?;
+    Ok((max_final, max_during))
+}
+
+pub struct LargestFinal;
+impl Answer for LargestFinal {
+    type Input = ByLine<Instruction>;
+    type Output = i32;
+    fn ans(&self, inputs: impl Iterator<Item = Instruction>) -> Result<Self::Output> {
+        Ok(largest_values(inputs)?.0)
+    }
+}
+
+pub struct LargestDuring;
+impl Answer for LargestDuring {
+    type Input = ByLine<Instruction>;
+    type Output = i32;
+    fn ans(&self, inputs: impl Iterator<Item = Instruction>) -> Result<Self::Output> {
+        Ok(largest_values(inputs)?.1)
+    }
+}
+
+examples! {
+    largest_final: LargestFinal, include_str!("../../input/2017/day8_test.txt") => 1;
+    largest_during: LargestDuring, include_str!("../../input/2017/day8_test.txt") => 10;
+}
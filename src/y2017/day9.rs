@@ -0,0 +1,88 @@
+use crate::examples;
+use crate::util::{Answer, Bytes, Error, Result};
+
+#[derive(Default)]
+struct Stream {
+    // Sum of the nesting depth of every group
+    score: usize,
+    // Non-cancelled characters within garbage
+    garbage: usize,
+}
+
+fn read_stream(inputs: impl Iterator<Item = u8>) -> Result<Stream> {
+    let mut stream = Stream::default();
+    let mut depth = 0;
+    let mut in_garbage = false;
+    let mut cancelled = false;
+    for b in inputs {
+        if cancelled {
+            cancelled = false;
+        } else if in_garbage {
+            match b {
+                b'!' => cancelled = true,
+                b'>' => in_garbage = false,
+                _ => stream.garbage += 1,
+            }
+        } else {
+            match b {
+                b'{' => {
+                    depth += 1;
+                    stream.score += depth;
+                }
+                b'}' => {
+                    depth = depth
+                        .checked_sub(1)
+                        .ok_or_else(|| Error::Parse("Unmatched '}'".to_string()))?;
+                }
+                b'<' => in_garbage = true,
+                b',' | b'\n' => {}
+                b => {
+                    return Err(Error::Parse(format!(
+                        "Unexpected {:?} outside of garbage",
+                        char::from(b)
+                    )))
+                }
+            }
+        }
+    }
+    if depth != 0 || in_garbage || cancelled {
+        return Err(Error::Parse("Unterminated stream".to_string()));
+    }
+    Ok(stream)
+}
+
+pub struct TotalScore;
+impl Answer for TotalScore {
+    type Input = Bytes;
+    type Output = usize;
+    fn ans(&self, inputs: impl Iterator<Item = u8>) -> Result<Self::Output> {
+        Ok(read_stream(inputs)?.score)
+    }
+}
+
+pub struct GarbageLen;
+impl Answer for GarbageLen {
+    type Input = Bytes;
+    type Output = usize;
+    fn ans(&self, inputs: impl Iterator<Item = u8>) -> Result<Self::Output> {
+        Ok(read_stream(inputs)?.garbage)
+    }
+}
+
+examples! {
+    score_one: TotalScore, "{}" => 1;
+    score_nested: TotalScore, "{{{}}}" => 6;
+    score_siblings: TotalScore, "{{},{}}" => 5;
+    score_mixed: TotalScore, "{{{},{},{{}}}}" => 16;
+    score_garbage: TotalScore, "{<a>,<a>,<a>,<a>}" => 1;
+    score_groups: TotalScore, "{{<ab>},{<ab>},{<ab>},{<ab>}}" => 9;
+    score_cancelled_bang: TotalScore, "{{<!!>},{<!!>},{<!!>},{<!!>}}" => 9;
+    score_cancelled_end: TotalScore, "{{<a!>},{<a!>},{<a!>},{<ab>}}" => 3;
+    garbage_empty: GarbageLen, "<>" => 0;
+    garbage_random: GarbageLen, "<random characters>" => 17;
+    garbage_open: GarbageLen, "<<<<>" => 3;
+    garbage_cancelled: GarbageLen, "<{!>}>" => 2;
+    garbage_bangs: GarbageLen, "<!!>" => 0;
+    garbage_bang_end: GarbageLen, "<!!!>>" => 0;
+    garbage_mixed: GarbageLen, r#"<{o"i!a,<{i<a>"# => 10;
+}
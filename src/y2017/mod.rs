@@ -0,0 +1,34 @@
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+
+use crate::util::Registry;
+
+const YEAR: i32 = 2017;
+
+pub fn register(r: &mut Registry) {
+    r.add(YEAR, 1, 1, day1::Sequential);
+    r.add(YEAR, 1, 2, day1::Halfway);
+    r.add(YEAR, 2, 1, day2::MinMaxDiff);
+    r.add(YEAR, 2, 2, day2::EvenDiv);
+    r.add(YEAR, 3, 1, day3::CountSteps);
+    r.add(YEAR, 3, 2, day3::FirstValue);
+    r.add(YEAR, 4, 1, day4::NoDuplicates);
+    r.add(YEAR, 4, 2, day4::NoAnagrams);
+    r.add(YEAR, 5, 1, day5::Adjust(day5::increment));
+    r.add(YEAR, 5, 2, day5::Adjust(day5::converge));
+    r.add(YEAR, 6, 1, day6::RepeatIdx);
+    r.add(YEAR, 6, 2, day6::CycleCount);
+    r.add(YEAR, 7, 1, day7::RootName);
+    r.add(YEAR, 7, 2, day7::CorrectWeight);
+    r.add(YEAR, 8, 1, day8::LargestFinal);
+    r.add(YEAR, 8, 2, day8::LargestDuring);
+    r.add(YEAR, 9, 1, day9::TotalScore);
+    r.add(YEAR, 9, 2, day9::GarbageLen);
+}
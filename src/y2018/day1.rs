@@ -1,5 +1,6 @@
 use std::iter;
 
+use crate::examples;
 use crate::util::{Answer, ByLine, IterExtra, Result};
 
 pub struct Sum;
@@ -27,3 +28,14 @@ impl Answer for FirstRepeat {
             .expect("chain should create infinite iterator"))
     }
 }
+
+examples! {
+    sum_mixed: Sum, "+1\n-2\n+3\n+1" => 3;
+    sum_positive: Sum, "+1\n+1\n+1" => 3;
+    sum_zero: Sum, "+1\n+1\n-2" => 0;
+    sum_negative: Sum, "-1\n-2\n-3" => -6;
+    repeat_zero: FirstRepeat, "+1\n-1" => 0;
+    repeat_ten: FirstRepeat, "+3\n+3\n+4\n-2\n-4" => 10;
+    repeat_five: FirstRepeat, "-6\n+3\n+8\n+5\n-6" => 5;
+    repeat_fourteen: FirstRepeat, "+7\n+7\n-2\n-7\n-4" => 14;
+}
@@ -1,12 +1,15 @@
 use std::str::FromStr;
 
-use euclid::Vector2D;
 use itertools::Itertools;
-use nom::*;
 
-use crate::util::{to_result, trim_parse, Answer, ByLine, Error, Result};
-
-type Point = Vector2D<i32>;
+use crate::examples;
+use crate::util::{
+    grid::{Grid, Point},
+    ocr,
+    parse::{self, delimited, number, preceded, separated_pair, tag, ws, PResult},
+    trace::{Quiet, Tracer},
+    Answer, ByLine, Error, Result,
+};
 
 #[derive(PartialEq, Eq, Hash)]
 pub struct Light {
@@ -14,85 +17,176 @@ pub struct Light {
     velocity: Point,
 }
 
-named!(point<&str,Point>, do_parse!(
-    tag!("<") >>
-    x: map_res!(take_until_and_consume!(","), trim_parse) >>
-    y: map_res!(take_until_and_consume!(">"), trim_parse) >>
-    (Point::new(x, y))
-));
+// "<x, y>" with any amount of padding before the numbers
+fn point(i: &str) -> PResult<'_, Point> {
+    let coords = separated_pair(ws(number), tag(","), ws(number));
+    let (i, (x, y)) = delimited(tag("<"), coords, tag(">"))(i)?;
+    Ok((i, Point::new(x, y)))
+}
 
-named!(light<&str,Light>, do_parse!(
-    tag!("position=") >> position: point >> tag!(" ") >>
-    tag!("velocity=") >> velocity: point >>
-    (Light{position, velocity})
-));
+fn light(i: &str) -> PResult<'_, Light> {
+    let (i, position) = preceded(tag("position="), point)(i)?;
+    let (i, velocity) = preceded(tag(" velocity="), point)(i)?;
+    Ok((i, Light { position, velocity }))
+}
 
 impl FromStr for Light {
     type Err = Error;
     fn from_str(s: &str) -> Result<Self> {
-        to_result(light(s))
+        parse::all(light, s)
     }
 }
 
-pub struct Sky(pub i32);
+fn bounds(lights: &[Light]) -> Option<(Point, Point)> {
+    let (x_min, x_max) = lights.iter().map(|l| l.position.x).minmax().into_option()?;
+    let (y_min, y_max) = lights.iter().map(|l| l.position.y).minmax().into_option()?;
+    Some((Point::new(x_min, y_min), Point::new(x_max, y_max)))
+}
 
-impl Sky {
-    fn contains(&self, p: Point) -> bool {
-        p.x.abs() < self.0 && p.y.abs() < self.0
-    }
+fn area(lights: &[Light]) -> i64 {
+    bounds(lights).map_or(0, |(min, max)| {
+        i64::from(max.x - min.x + 1) * i64::from(max.y - min.y + 1)
+    })
 }
 
-fn plot(lights: &[Light]) {
-    let (x_min, x_max) = lights
-        .iter()
-        .map(|l| l.position.x)
-        .minmax()
-        .into_option()
-        .unwrap();
-    let (y_min, y_max) = lights
-        .iter()
-        .map(|l| l.position.y)
-        .minmax()
-        .into_option()
-        .unwrap();
-
-    let mut canvas = [[false; 100]; 100];
+const NO_LIGHTS: Error = Error::Custom("No lights in input");
+
+// Moves the lights until they are closest together, which is when they spell
+// out the message. Returns the number of seconds taken.
+fn converge(lights: &mut [Light]) -> Result<usize> {
+    if lights.is_empty() {
+        return Err(NO_LIGHTS);
+    }
+    let mut seconds = 0;
+    let mut last = area(lights);
+    loop {
+        for l in lights.iter_mut() {
+            l.position += l.velocity;
+        }
+        let next = area(lights);
+        if next >= last {
+            // Step back to the smallest arrangement
+            for l in lights.iter_mut() {
+                l.position -= l.velocity;
+            }
+            return Ok(seconds);
+        }
+        last = next;
+        seconds += 1;
+    }
+}
 
+fn render(lights: &[Light]) -> Grid<char> {
+    let Some((min, max)) = bounds(lights) else {
+        return Grid::filled(0, 0, '.');
+    };
+    let size = max - min + Point::new(1, 1);
+    let mut sky = Grid::filled(size.x as usize, size.y as usize, '.');
     for light in lights {
-        let x = ((light.position.x - x_min) * 100) / (x_max - x_min + 1);
-        let y = ((light.position.y - y_min) * 100) / (y_max - y_min + 1);
-        canvas[x as usize][(99 - y) as usize] = true;
+        sky[light.position - min] = '#';
     }
+    sky
+}
 
-    for row in canvas.iter() {
-        for occupied in row.iter() {
-            print!("{}", if *occupied { '#' } else { '.' });
+pub struct Message;
+impl Answer for Message {
+    type Input = ByLine<Light>;
+    type Output = String;
+    fn ans(&self, inputs: impl Iterator<Item = Light>) -> Result<Self::Output> {
+        self.ans_traced(inputs, &mut Quiet)
+    }
+    fn ans_traced(
+        &self,
+        inputs: impl Iterator<Item = Light>,
+        tracer: &mut dyn Tracer,
+    ) -> Result<Self::Output> {
+        let mut lights: Vec<_> = inputs.collect();
+        let seconds = converge(&mut lights)?;
+        let sky = render(&lights);
+        tracer.frame(format_args!("Second {seconds}"), &sky);
+        // Letters in other fonts, like the example's, are shown as drawn.
+        if !ocr::has_font(sky.height()) {
+            return Ok(sky.to_string());
         }
-        println!();
+        ocr::read_points(lights.iter().map(|l| l.position))
     }
 }
 
-impl Answer for Sky {
+pub struct Seconds;
+impl Answer for Seconds {
     type Input = ByLine<Light>;
     type Output = usize;
     fn ans(&self, inputs: impl Iterator<Item = Light>) -> Result<Self::Output> {
-        let mut count = 0;
         let mut lights: Vec<_> = inputs.collect();
-        while !lights.is_empty() {
-            count += 1;
-            lights.drain_filter(|l| {
-                l.position += l.velocity;
-                !self.contains(l.position)
-            });
-
-            // Found via repeated iteration
-            if count == 10_312 {
-                plot(&lights);
-                println!();
-                break;
-            }
-        }
+        converge(&mut lights)
+    }
+}
 
-        Ok(count)
+#[cfg(test)]
+const EXAMPLE: &str = "\
+position=< 9,  1> velocity=< 0,  2>
+position=< 7,  0> velocity=<-1,  0>
+position=< 3, -2> velocity=<-1,  1>
+position=< 6, 10> velocity=<-2, -1>
+position=< 2, -4> velocity=< 2,  2>
+position=<-6, 10> velocity=< 2, -2>
+position=< 1,  8> velocity=< 1, -1>
+position=< 1,  7> velocity=< 1,  0>
+position=<-3, 11> velocity=< 1, -2>
+position=< 7,  6> velocity=<-1, -1>
+position=<-2,  3> velocity=< 1,  0>
+position=<-4,  3> velocity=< 2,  0>
+position=<10, -3> velocity=<-1,  1>
+position=< 5, 11> velocity=< 1, -2>
+position=< 4,  7> velocity=< 0, -1>
+position=< 8, -2> velocity=< 0,  1>
+position=<15,  0> velocity=<-2,  0>
+position=< 1,  6> velocity=< 1,  0>
+position=< 8,  9> velocity=< 0, -1>
+position=< 3,  3> velocity=<-1,  1>
+position=< 0,  5> velocity=< 0, -1>
+position=<-2,  2> velocity=< 2,  0>
+position=< 5, -2> velocity=< 1,  2>
+position=< 1,  4> velocity=< 2,  1>
+position=<-2,  7> velocity=< 2, -2>
+position=< 3,  6> velocity=<-1, -1>
+position=< 5,  0> velocity=< 1,  0>
+position=<-6,  0> velocity=< 2,  0>
+position=< 5,  9> velocity=< 1, -2>
+position=<14,  7> velocity=<-2,  0>
+position=<-3,  6> velocity=< 2, -1>";
+
+// The example's message uses a smaller font than real inputs.
+#[cfg(test)]
+const HI: &str = "\
+#...#..###
+#...#...#.
+#...#...#.
+#####...#.
+#...#...#.
+#...#...#.
+#...#...#.
+#...#..###";
+
+examples! {
+    message: Message, EXAMPLE => HI;
+    seconds: Seconds, EXAMPLE => 3;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::Solver;
+
+    #[test]
+    fn unknown_letter() {
+        // A bar as tall as the large font, which has no such letter.
+        let input = (0..10)
+            .map(|y| format!("position=<0, {y}> velocity=<0, 0>"))
+            .join("\n");
+        match Message.solve(&mut input.as_bytes()) {
+            Err(e) => assert!(e.to_string().contains("Unrecognized letters")),
+            Ok(ans) => panic!("expected an error, got {}", ans),
+        }
     }
 }
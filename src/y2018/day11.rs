@@ -1,53 +1,44 @@
 use itertools::iproduct;
 
-use crate::util::{Answer, ByWhitespace, IterExtra, Result};
+use crate::examples;
+use crate::util::{grid::Grid, summed_area::SummedArea, Answer, ByWhitespace, IterExtra, Result};
 
-struct Grid {
+struct FuelCells {
     size: usize,
-    levels: Vec<i32>,
+    power: SummedArea<i32>,
 }
 
-impl Grid {
-    fn new(size: usize) -> Self {
-        Self {
-            size,
-            levels: vec![0; size * size],
-        }
-    }
-    fn compute(&mut self, serial: i32) {
-        for (x, y) in iproduct!(0..self.size, 0..self.size) {
-            let rack_id = (x + 1) as i32 + 10;
-            let mut power = rack_id * (y + 1) as i32;
+impl FuelCells {
+    fn new(size: usize, serial: i32) -> Self {
+        let levels = Grid::from_fn(size, size, |p| {
+            let rack_id = (p.x + 1) + 10;
+            let mut power = rack_id * (p.y + 1);
             power += serial;
             power *= rack_id;
             power = (power / 100) % 10;
-            power -= 5;
-
-            self.levels[x * self.size + y] = power;
-        }
+            power - 5
+        });
+        let power = SummedArea::new(&levels);
+        Self { size, power }
     }
-    fn find(&self, sz: usize) -> (usize, usize) {
+    // Returns the total power and top-left corner of the best square of
+    // size sz.
+    fn find(&self, sz: usize) -> (i32, usize, usize) {
         let points = iproduct!(0..=(self.size - sz), 0..=(self.size - sz));
         points
-            .max_by_key(|&(x, y)| -> i32 {
-                iproduct!(x..(x + sz), y..(y + sz))
-                    .map(|(xi, yi)| self.levels[xi * self.size + yi])
-                    .sum()
-            })
+            .map(|(x, y)| (self.power.sum(x..(x + sz), y..(y + sz)), x, y))
+            .max_by_key(|&(power, _, _)| power)
             .unwrap()
     }
-
     fn overall_find(&self) -> (usize, usize, usize) {
-        (1..(self.size))
-            .flat_map(|sz| {
-                iproduct!(0..=(self.size - sz), 0..=(self.size - sz)).map(move |(x, y)| (x, y, sz))
+        let (_, x, y, sz) = (1..=self.size)
+            .map(|sz| {
+                let (power, x, y) = self.find(sz);
+                (power, x, y, sz)
             })
-            .max_by_key(|&(x, y, sz)| -> i32 {
-                iproduct!(x..(x + sz), y..(y + sz))
-                    .map(|(xi, yi)| self.levels[xi * self.size + yi])
-                    .sum()
-            })
-            .unwrap()
+            .max_by_key(|&(power, _, _, _)| power)
+            .unwrap();
+        (x, y, sz)
     }
 }
 
@@ -56,9 +47,8 @@ impl Answer for LargestPower {
     type Input = ByWhitespace<i32>;
     type Output = String;
     fn ans(&self, inputs: impl Iterator<Item = i32>) -> Result<Self::Output> {
-        let mut g = Grid::new(self.0);
-        g.compute(inputs.single()?);
-        let (x, y) = g.find(self.1);
+        let cells = FuelCells::new(self.0, inputs.single()?);
+        let (_, x, y) = cells.find(self.1);
         Ok(format!("{},{}", x + 1, y + 1))
     }
 }
@@ -68,9 +58,15 @@ impl Answer for Overall {
     type Input = ByWhitespace<i32>;
     type Output = String;
     fn ans(&self, inputs: impl Iterator<Item = i32>) -> Result<Self::Output> {
-        let mut g = Grid::new(self.0);
-        g.compute(inputs.single()?);
-        let (x, y, sz) = g.overall_find();
+        let cells = FuelCells::new(self.0, inputs.single()?);
+        let (x, y, sz) = cells.overall_find();
         Ok(format!("{},{},{}", x + 1, y + 1, sz))
     }
 }
+
+examples! {
+    largest_power_18: LargestPower(300, 3), "18" => "33,45";
+    largest_power_42: LargestPower(300, 3), "42" => "21,61";
+    overall_18: Overall(300), "18" => "90,269,16";
+    overall_42: Overall(300), "42" => "232,251,12";
+}
@@ -1,66 +1,243 @@
-use std::collections::HashSet;
+use std::{convert::TryFrom, fmt, str::FromStr};
 
-use crate::util::{Answer, Lines, Result};
+use itertools::Itertools;
 
-fn parse(c: char) -> bool {
-    c == '#'
+use crate::examples;
+use crate::util::{
+    cycle::Drift,
+    parse::{self, alt, fail, map, preceded, separated_pair, tag, PResult},
+    trace::{Quiet, Tracer},
+    Answer, ByLine, Error, Result,
+};
+
+fn pot(i: &str) -> PResult<'_, bool> {
+    alt(map(tag("#"), |_| true), map(tag("."), |_| false))(i)
+}
+
+// Five pots as a bitmask, with the leftmost pot as the highest bit.
+fn pattern(mut i: &str) -> PResult<'_, usize> {
+    let mut mask = 0;
+    for _ in 0..5 {
+        let (rest, p) = pot(i)?;
+        mask = (mask << 1) | usize::from(p);
+        i = rest;
+    }
+    Ok((i, mask))
+}
+
+fn pots(mut i: &str) -> PResult<'_, Vec<bool>> {
+    let mut pots = Vec::new();
+    while let Ok((rest, p)) = pot(i) {
+        pots.push(p);
+        i = rest;
+    }
+    if pots.is_empty() {
+        return fail(i, "pots");
+    }
+    Ok((i, pots))
+}
+
+pub enum Line {
+    Initial(Vec<bool>),
+    Rule(usize, bool),
+    Blank,
 }
 
-fn get(state: &[bool], i: i64) -> bool {
-    if i < 0 {
-        return false;
+fn line(i: &str) -> PResult<'_, Line> {
+    if i.is_empty() {
+        return Ok((i, Line::Blank));
     }
-    *state.get(i as usize).unwrap_or(&false)
+    let initial = map(preceded(tag("initial state: "), pots), Line::Initial);
+    let rule = map(separated_pair(pattern, tag(" => "), pot), |(m, p)| {
+        Line::Rule(m, p)
+    });
+    alt(initial, rule)(i)
 }
 
-type Mapping = HashSet<Vec<bool>>;
+impl FromStr for Line {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        parse::all(line, s)
+    }
+}
 
-fn new_mapping(inputs: impl Iterator<Item = String>) -> Mapping {
-    inputs
-        .map(|input| input.chars().take(5).map(parse).collect())
+// Whether a pot has a plant next generation, indexed by the pattern around it.
+type Rules = [bool; 32];
+
+fn show(mask: usize) -> String {
+    (0..5)
+        .rev()
+        .map(|b| if mask >> b & 1 == 1 { '#' } else { '.' })
         .collect()
 }
 
-fn has_plant(m: &Mapping, state: &[bool], i: i64) -> bool {
-    let key: Vec<_> = ((i - 2)..=(i + 2)).map(|idx| get(state, idx)).collect();
-    m.contains(&key)
+// Reads the initial state and rules. Every pattern must have a rule.
+fn read(inputs: impl Iterator<Item = Line>) -> Result<(Vec<bool>, Rules)> {
+    let mut initial = None;
+    let mut rules = [None; 32];
+    for line in inputs {
+        match line {
+            Line::Initial(_) if initial.is_some() => {
+                return Err(Error::Custom("Multiple initial states"))
+            }
+            Line::Initial(pots) => initial = Some(pots),
+            Line::Rule(mask, plant) => match rules[mask].replace(plant) {
+                Some(old) if old != plant => {
+                    return Err(Error::Message(format!(
+                        "Conflicting rules for {}",
+                        show(mask)
+                    )))
+                }
+                _ => {}
+            },
+            Line::Blank => {}
+        }
+    }
+    let initial = initial.ok_or(Error::Custom("No initial state"))?;
+
+    let missing: Vec<_> = (0..32).filter(|&m| rules[m].is_none()).collect();
+    if !missing.is_empty() {
+        return Err(Error::Message(format!(
+            "No rules for {}",
+            missing.into_iter().map(show).format(", ")
+        )));
+    }
+    if rules[0] == Some(true) {
+        return Err(Error::Custom(
+            "Rule \".....\" would grow infinitely many plants",
+        ));
+    }
+    Ok((initial, rules.map(|r| r.unwrap_or(false))))
+}
+
+// Pots from the first to the last plant, so a pattern that moves along
+// keeps the same pots.
+#[derive(Clone)]
+struct Plants {
+    // Number of the first pot
+    offset: i64,
+    pots: Vec<bool>,
 }
 
-const INITIAL: &str = "#.#.#....##...##...##...#.##.#.###...#.##...#....#.#...#.##.........#.#...#..##.#.....#..#.###";
+impl Plants {
+    fn new(offset: i64, mut pots: Vec<bool>) -> Self {
+        let trailing = pots.iter().rev().take_while(|&&p| !p).count();
+        pots.truncate(pots.len() - trailing);
+        let leading = pots.iter().take_while(|&&p| !p).count();
+        pots.drain(..leading);
+        Self {
+            offset: offset + leading as i64,
+            pots,
+        }
+    }
 
-pub struct PlantSum(pub i64);
+    fn get(&self, i: i64) -> bool {
+        usize::try_from(i)
+            .ok()
+            .and_then(|i| self.pots.get(i))
+            .copied()
+            .unwrap_or(false)
+    }
+
+    fn step(&self, rules: &Rules) -> Self {
+        let len = self.pots.len() as i64;
+        let mut mask = 0;
+        let pots = ((-2)..(len + 2))
+            .map(|i| {
+                mask = ((mask << 1) | usize::from(self.get(i + 2))) & 0b11111;
+                rules[mask]
+            })
+            .collect();
+        Self::new(self.offset - 2, pots)
+    }
+
+    fn sum(&self) -> i64 {
+        (self.pots.iter().enumerate())
+            .filter(|(_, &plant)| plant)
+            .map(|(i, _)| i as i64 + self.offset)
+            .sum()
+    }
+}
+
+// The number of the first pot, then the pots up to the last plant.
+impl fmt::Display for Plants {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:>4}: ", self.offset)?;
+        for &plant in &self.pots {
+            f.write_str(if plant { "#" } else { "." })?;
+        }
+        Ok(())
+    }
+}
+
+pub struct PlantSum(pub usize);
 impl Answer for PlantSum {
-    type Input = Lines;
+    type Input = ByLine<Line>;
     type Output = i64;
-    fn ans(&self, inputs: impl Iterator<Item = String>) -> Result<Self::Output> {
-        let mut state: Vec<bool> = INITIAL.chars().map(parse).collect();
-        let mut num_before: i64 = 0;
-        let mut len = state.len() as i64;
+    fn ans(&self, inputs: impl Iterator<Item = Line>) -> Result<Self::Output> {
+        self.ans_traced(inputs, &mut Quiet)
+    }
+    fn ans_traced(
+        &self,
+        inputs: impl Iterator<Item = Line>,
+        tracer: &mut dyn Tracer,
+    ) -> Result<Self::Output> {
+        let (initial, rules) = read(inputs)?;
+        let initial = Plants::new(0, initial);
+        tracer.frame(format_args!("Generation 0"), &initial);
 
-        let mapping = new_mapping(inputs);
+        // Eventually the plants settle into a pattern moving at a fixed speed,
+        // though not always before the generation asked for.
+        let mut generation = 0;
+        let drift = Drift::new(
+            initial,
+            self.0,
+            |plants| {
+                let next = plants.step(&rules);
+                generation += 1;
+                tracer.frame(format_args!("Generation {generation}"), &next);
+                next
+            },
+            |plants| plants.pots.clone(),
+            Plants::sum,
+        );
+        Ok(drift.value_at(self.0))
+    }
+}
 
-        for _ in 0..self.0 {
-            state = ((-2)..(len + 2))
-                .map(|i| has_plant(&mapping, &state, i))
-                .collect();
+#[cfg(test)]
+const EXAMPLE: &str = include_str!("../../input/2018/day12_test.txt");
 
-            num_before += 2;
-            len += 4;
-        }
+// The example's patterns, with rules under which the plants spread out
+// forever, so the pattern never repeats.
+#[cfg(test)]
+fn spreading() -> String {
+    let rules = EXAMPLE.lines().skip(2).map(|line| {
+        let pattern = &line[..5];
+        let plant = ["....#", "#....", "..#.."].contains(&pattern);
+        format!("{} => {}", pattern, if plant { '#' } else { '.' })
+    });
+    let mut lines = vec!["initial state: ...#".to_string(), String::new()];
+    lines.extend(rules);
+    lines.join("\n")
+}
 
-        // Long term pattern is: SUM = 508 + 20*G
+examples! {
+    plant_sum: PlantSum(20), EXAMPLE => 325;
+    no_repeat: PlantSum(20), &spreading() => 12;
+}
 
-        let sum: i64 = state
-            .iter()
-            .enumerate()
-            .filter_map(|(i, &plant)| {
-                if plant {
-                    Some(i as i64 - num_before)
-                } else {
-                    None
-                }
-            })
-            .sum();
-        Ok(sum)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_rule() {
+        let lines = EXAMPLE.lines().filter(|l| !l.starts_with("..#.#"));
+        let result = read(lines.map(|l| l.parse().unwrap()));
+        match result {
+            Err(e) => assert_eq!(e.to_string(), "No rules for ..#.#"),
+            Ok(_) => panic!("expected an error for the missing rule"),
+        }
     }
 }
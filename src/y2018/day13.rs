@@ -1,12 +1,13 @@
-use std::{cmp::Ordering, collections::BinaryHeap};
+use std::fmt;
 
-use euclid::Vector2D;
+use crate::examples;
+use crate::util::{
+    grid::{Grid, Point},
+    trace::{Quiet, Tracer},
+    Answer, Error, Lines, Result,
+};
 
-use crate::util::{Answer, Error, IterExtra, Lines, Result};
-
-type Point = Vector2D<i32>;
-type Map = Vec<Vec<State>>;
-type Queue = BinaryHeap<Cart>;
+type Map = Grid<State>;
 
 #[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
 enum Direction {
@@ -19,28 +20,28 @@ enum Direction {
 impl Direction {
     fn unit(self) -> Point {
         match self {
-            Direction::Up => Point::new(0, -1),
-            Direction::Left => Point::new(-1, 0),
-            Direction::Down => Point::new(0, 1),
-            Direction::Right => Point::new(1, 0),
+            Self::Up => Point::new(0, -1),
+            Self::Left => Point::new(-1, 0),
+            Self::Down => Point::new(0, 1),
+            Self::Right => Point::new(1, 0),
         }
     }
-    fn turn(&mut self, t: Turn) {
+    const fn turn(&mut self, t: Turn) {
         *self = match t {
             Turn::Straight => return,
             // Turning "Left"  makes the directions go counterclockwise.
             Turn::Left => match *self {
-                Direction::Up => Direction::Left,
-                Direction::Left => Direction::Down,
-                Direction::Down => Direction::Right,
-                Direction::Right => Direction::Up,
+                Self::Up => Self::Left,
+                Self::Left => Self::Down,
+                Self::Down => Self::Right,
+                Self::Right => Self::Up,
             },
             // Turning "Right"  makes the directions go clockwise.
             Turn::Right => match *self {
-                Direction::Up => Direction::Right,
-                Direction::Right => Direction::Down,
-                Direction::Down => Direction::Left,
-                Direction::Left => Direction::Up,
+                Self::Up => Self::Right,
+                Self::Right => Self::Down,
+                Self::Down => Self::Left,
+                Self::Left => Self::Up,
             },
         };
     }
@@ -68,7 +69,7 @@ fn decode(c: char) -> Result<(State, Option<Direction>)> {
         '\\' => Ok((State::UlLr, None)),
         '/' => Ok((State::UrLl, None)),
         ' ' => Ok((State::None, None)),
-        _ => Err(Error::Parse(format!("Unknown character: {}", c))),
+        _ => Err(Error::Parse(format!("Unknown character: {c}"))),
     }
 }
 
@@ -80,32 +81,34 @@ enum Turn {
 }
 
 impl Turn {
-    fn next(&mut self) {
+    const fn next(&mut self) {
         *self = match *self {
-            Turn::Left => Turn::Straight,
-            Turn::Straight => Turn::Right,
-            Turn::Right => Turn::Left,
+            Self::Left => Self::Straight,
+            Self::Straight => Self::Right,
+            Self::Right => Self::Left,
         };
     }
 }
 
-#[derive(Eq, Debug)]
+#[derive(Debug)]
 struct Cart {
     pos: Point,
     dir: Direction,
     turn: Turn,
-    gen: usize,
+    crashed: bool,
 }
 
 impl Cart {
-    fn step(&mut self, map: &[Vec<State>]) -> Result<()> {
-        self.gen += 1;
+    fn step(&mut self, map: &Map) -> Result<()> {
         self.pos += self.dir.unit();
-        match map.get(self.pos.y as usize)?.get(self.pos.x as usize)? {
-            State::None => {
-                return Err(Error::Custom("We have left the track"));
+        match map.get(self.pos) {
+            None | Some(State::None) => {
+                return Err(Error::Message(format!(
+                    "Cart left the track at {},{}",
+                    self.pos.x, self.pos.y
+                )));
             }
-            State::UlLr => {
+            Some(State::UlLr) => {
                 // Change direction "\"
                 self.dir = match self.dir {
                     Direction::Up => Direction::Left,
@@ -114,7 +117,7 @@ impl Cart {
                     Direction::Right => Direction::Down,
                 }
             }
-            State::UrLl => {
+            Some(State::UrLl) => {
                 // Change direction "/"
                 self.dir = match self.dir {
                     Direction::Up => Direction::Right,
@@ -123,78 +126,133 @@ impl Cart {
                     Direction::Right => Direction::Up,
                 }
             }
-            State::Plus => {
+            Some(State::Plus) => {
                 self.dir.turn(self.turn);
                 self.turn.next();
             }
             _ => {}
-        };
+        }
         Ok(())
     }
 }
 
-impl PartialEq for Cart {
-    fn eq(&self, other: &Self) -> bool {
-        self.cmp(other) == Ordering::Equal
-    }
+struct Tracks {
+    map: Map,
+    carts: Vec<Cart>,
+    // Index of the cart at each position, so collisions are found as soon
+    // as a cart moves onto another one.
+    occupied: Grid<Option<usize>>,
+    remaining: usize,
+    ticks: usize,
 }
 
-impl PartialOrd for Cart {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+impl Tracks {
+    fn new(inputs: impl Iterator<Item = String>) -> Result<Self> {
+        let tiles = Grid::parse_padded(inputs, (State::None, None), decode)?;
+        let carts: Vec<_> = tiles
+            .iter()
+            .filter_map(|(pos, &(_, dir))| {
+                Some(Cart {
+                    pos,
+                    dir: dir?,
+                    turn: Turn::Left,
+                    crashed: false,
+                })
+            })
+            .collect();
+        let mut occupied = tiles.map(|_| None);
+        for (i, cart) in carts.iter().enumerate() {
+            occupied[cart.pos] = Some(i);
+        }
+        Ok(Self {
+            map: tiles.map(|&(state, _)| state),
+            remaining: carts.len(),
+            carts,
+            occupied,
+            ticks: 0,
+        })
     }
-}
 
-impl Ord for Cart {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.gen
-            .cmp(&other.gen)
-            .then(self.pos.y.cmp(&other.pos.y))
-            .then(self.pos.x.cmp(&other.pos.x))
-            .reverse()
+    /// Moves every cart once, in reading order of their positions at the
+    /// start of the tick. Returns where the first crash happened, if any.
+    fn tick(&mut self) -> Result<Option<Point>> {
+        let mut order: Vec<_> = (0..self.carts.len())
+            .filter(|&i| !self.carts[i].crashed)
+            .collect();
+        order.sort_by_key(|&i| (self.carts[i].pos.y, self.carts[i].pos.x));
+
+        let mut first_crash = None;
+        for i in order {
+            // Hit by an earlier cart this tick
+            if self.carts[i].crashed {
+                continue;
+            }
+            self.occupied[self.carts[i].pos] = None;
+            self.carts[i].step(&self.map)?;
+            let pos = self.carts[i].pos;
+            if let Some(other) = self.occupied[pos].take() {
+                self.carts[i].crashed = true;
+                self.carts[other].crashed = true;
+                self.remaining -= 2;
+                first_crash.get_or_insert(pos);
+            } else {
+                self.occupied[pos] = Some(i);
+            }
+        }
+        self.ticks += 1;
+        Ok(first_crash)
+    }
+
+    fn trace(&self, tracer: &mut dyn Tracer) {
+        tracer.frame(format_args!("Tick {}", self.ticks), self);
     }
 }
 
-fn make_map(inputs: impl Iterator<Item = String>) -> Result<(Map, Queue)> {
-    let mut carts = Queue::new();
-    let map = inputs
-        .enumerate()
-        .map(|(y, input)| {
-            let row = input.chars().enumerate();
-            row.map(|(x, c)| {
-                let (state, dir) = decode(c)?;
-                if let Some(d) = dir {
-                    let cart = Cart {
-                        pos: Point::new(x as i32, y as i32),
-                        dir: d,
-                        turn: Turn::Left,
-                        gen: 0,
-                    };
-                    carts.push(cart)
-                }
-                Ok(state)
-            })
-            .collect()
-        })
-        .collect::<Result<Map>>()?;
-    Ok((map, carts))
+// Draws the track with the remaining carts on it.
+impl fmt::Display for Tracks {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut picture = self.map.map(|state| match state {
+            State::None => ' ',
+            State::Vert => '|',
+            State::Horz => '-',
+            State::Plus => '+',
+            State::UlLr => '\\',
+            State::UrLl => '/',
+        });
+        for cart in self.carts.iter().filter(|c| !c.crashed) {
+            picture[cart.pos] = match cart.dir {
+                Direction::Up => '^',
+                Direction::Down => 'v',
+                Direction::Left => '<',
+                Direction::Right => '>',
+            };
+        }
+        write!(f, "{picture}")
+    }
 }
 
 pub struct FirstCrash;
 impl Answer for FirstCrash {
     type Input = Lines;
-    type Output = String;
+    type Output = Point;
     fn ans(&self, inputs: impl Iterator<Item = String>) -> Result<Self::Output> {
-        let (map, mut carts) = make_map(inputs)?;
-
+        self.ans_traced(inputs, &mut Quiet)
+    }
+    fn ans_traced(
+        &self,
+        inputs: impl Iterator<Item = String>,
+        tracer: &mut dyn Tracer,
+    ) -> Result<Self::Output> {
+        let mut tracks = Tracks::new(inputs)?;
+        if tracks.carts.len() < 2 {
+            return Err(Error::Custom("At least two carts are needed to crash"));
+        }
         loop {
-            // Potential bug, will not work for carts colliding on different generations.
-            if let Ok(collision) = carts.iter().map(|c| c.pos).duplicates().single() {
-                return Ok(format!("{}", collision));
+            tracks.trace(tracer);
+            if let Some(crash) = tracks.tick()? {
+                tracks.trace(tracer);
+                return Ok(crash);
             }
-
-            let mut top = carts.peek_mut().ok_or(Error::Custom("No carts present"))?;
-            top.step(&map)?;
         }
     }
 }
@@ -202,25 +260,40 @@ impl Answer for FirstCrash {
 pub struct LastCart;
 impl Answer for LastCart {
     type Input = Lines;
-    type Output = String;
+    type Output = Point;
     fn ans(&self, inputs: impl Iterator<Item = String>) -> Result<Self::Output> {
-        let (map, mut carts) = make_map(inputs)?;
-
+        self.ans_traced(inputs, &mut Quiet)
+    }
+    fn ans_traced(
+        &self,
+        inputs: impl Iterator<Item = String>,
+        tracer: &mut dyn Tracer,
+    ) -> Result<Self::Output> {
+        let mut tracks = Tracks::new(inputs)?;
         loop {
-            carts = carts
-                .into_iter()
-                .only_uniques_by(|c| c.pos)
-                .into_iter()
-                .collect();
-            if carts.len() < 2 {
-                let mut last = carts.pop().ok_or(Error::Custom("All carts collided"))?;
-                let pos = last.pos;
-                last.step(&map)?;
-                return Ok(format!("{} or {}", pos, last.pos));
+            tracks.trace(tracer);
+            match tracks.remaining {
+                0 => return Err(Error::Custom("All carts collided")),
+                1 => {
+                    let last = tracks.carts.iter().find(|c| !c.crashed).unwrap();
+                    return Ok(last.pos);
+                }
+                _ => {}
             }
-
-            let mut top = carts.peek_mut().unwrap();
-            top.step(&map)?;
+            tracks.tick()?;
         }
     }
 }
+
+examples! {
+    first_crash: FirstCrash, include_str!("../../input/2018/day13_test.txt") => "(7,3)";
+    // The first cart moves onto the second before it gets to move away
+    swap_crash: FirstCrash, "-><-" => "(2,0)";
+    last_cart: LastCart, include_str!("../../input/2018/day13_test2.txt") => "(6,4)";
+    // Rows can be ragged once trailing spaces are trimmed
+    trimmed: LastCart, &include_str!("../../input/2018/day13_test2.txt")
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n") => "(6,4)";
+}
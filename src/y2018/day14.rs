@@ -1,73 +1,169 @@
-use crate::util::{Answer, ByWhitespace, Lines, Result};
+use crate::examples;
+use crate::util::{Answer, ByWhitespace, Error, Lines, Result};
 
-struct Recipies {
+/// The starting scoreboard, with elf `i` starting on recipe `i`.
+#[derive(Copy, Clone, Debug)]
+pub struct Kitchen {
+    pub seeds: &'static [u8],
+    pub elves: usize,
+}
+
+pub const KITCHEN: Kitchen = Kitchen {
+    seeds: &[3, 7],
+    elves: 2,
+};
+
+/// Every score on the scoreboard in order, making new recipes as needed.
+pub struct Recipies {
     scores: Vec<u8>,
-    active: [usize; 2],
+    elves: Vec<usize>,
+    next: usize,
 }
 
 impl Recipies {
-    fn new() -> Self {
-        Self {
-            scores: vec![3, 7],
-            active: [0, 1],
+    pub fn new(kitchen: Kitchen) -> Result<Self> {
+        if kitchen.elves == 0 || kitchen.elves > kitchen.seeds.len() {
+            return Err(Error::Custom("Every elf needs a seed recipe"));
+        }
+        if kitchen.seeds.iter().any(|&s| s > 9) {
+            return Err(Error::Custom("Scores must be single digits"));
         }
+        Ok(Self {
+            scores: kitchen.seeds.to_vec(),
+            elves: (0..kitchen.elves).collect(),
+            next: 0,
+        })
     }
 
     fn combine(&mut self) {
-        let s: u8 = self.active.iter().map(|&i| self.scores[i]).sum();
-
-        if s >= 10 {
-            self.scores.push(s / 10);
-            self.scores.push(s % 10);
-        } else {
-            self.scores.push(s)
+        let sum: usize = self.elves.iter().map(|&i| self.scores[i] as usize).sum();
+        let start = self.scores.len();
+        let mut rest = sum;
+        loop {
+            self.scores.push((rest % 10) as u8);
+            rest /= 10;
+            if rest == 0 {
+                break;
+            }
         }
+        self.scores[start..].reverse();
 
-        for i in &mut self.active {
+        for i in &mut self.elves {
             *i = (*i + self.scores[*i] as usize + 1) % self.scores.len();
         }
     }
 }
 
-pub struct ScoreList(pub usize);
-impl Answer for ScoreList {
-    type Input = ByWhitespace<usize>;
-    type Output = String;
-    fn ans(&self, mut inputs: impl Iterator<Item = usize>) -> Result<Self::Output> {
-        let input = inputs.next()?;
+impl Iterator for Recipies {
+    type Item = u8;
+    fn next(&mut self) -> Option<u8> {
+        while self.next >= self.scores.len() {
+            self.combine();
+        }
+        self.next += 1;
+        Some(self.scores[self.next - 1])
+    }
+}
+
+/// Knuth-Morris-Pratt search over a stream, fed one item at a time.
+struct Matcher<T> {
+    needle: Vec<T>,
+    // Length of the longest proper prefix of needle[..=i] that is also a
+    // suffix of it
+    fallback: Vec<usize>,
+    // How much of the needle the stream currently ends with
+    matched: usize,
+}
 
-        let mut recipies = Recipies::new();
+impl<T: Eq> Matcher<T> {
+    fn new(needle: Vec<T>) -> Self {
+        let mut fallback = vec![0; needle.len()];
+        let mut k = 0;
+        for i in 1..needle.len() {
+            while k > 0 && needle[i] != needle[k] {
+                k = fallback[k - 1];
+            }
+            if needle[i] == needle[k] {
+                k += 1;
+            }
+            fallback[i] = k;
+        }
+        Self {
+            needle,
+            fallback,
+            matched: 0,
+        }
+    }
 
-        while recipies.scores.len() < input + self.0 {
-            recipies.combine()
+    /// Returns whether the stream now ends with the needle.
+    fn push(&mut self, item: &T) -> bool {
+        if self.matched == self.needle.len() {
+            self.matched = self.fallback[self.matched - 1];
         }
+        while self.matched > 0 && self.needle[self.matched] != *item {
+            self.matched = self.fallback[self.matched - 1];
+        }
+        if self.needle[self.matched] == *item {
+            self.matched += 1;
+        }
+        self.matched == self.needle.len()
+    }
+}
 
-        let output = recipies.scores[input..input + self.0]
-            .iter()
-            .map(|d| -> char { (d + b'0').into() })
+const NO_INPUT: Error = Error::Custom("No input");
+
+pub struct ScoreList(pub Kitchen, pub usize);
+impl Answer for ScoreList {
+    type Input = ByWhitespace<usize>;
+    type Output = String;
+    fn ans(&self, mut inputs: impl Iterator<Item = usize>) -> Result<Self::Output> {
+        let input = inputs.next().ok_or(NO_INPUT)?;
+        let output = Recipies::new(self.0)?
+            .skip(input)
+            .take(self.1)
+            .map(|d| char::from(d + b'0'))
             .collect();
         Ok(output)
     }
 }
 
-pub struct FirstOccurance;
+pub struct FirstOccurance(pub Kitchen);
 impl Answer for FirstOccurance {
     type Input = Lines;
     type Output = usize;
     fn ans(&self, mut inputs: impl Iterator<Item = String>) -> Result<Self::Output> {
-        let needle: Vec<u8> = inputs.next()?.bytes().map(|b| b - b'0').collect();
+        let needle = inputs
+            .next()
+            .ok_or(NO_INPUT)?
+            .chars()
+            .map(|c| c.to_digit(10).map(|d| d as u8))
+            .collect::<Option<Vec<_>>>()
+            .ok_or(Error::Custom("Scores must be digits"))?;
+        if needle.is_empty() {
+            return Err(Error::Custom("No scores to find"));
+        }
 
-        let mut recipies = Recipies::new();
-        loop {
-            if needle.len() < recipies.scores.len() {
-                let c = recipies.scores.len() - needle.len();
-                for &cand in &[c, c - 1] {
-                    if recipies.scores[cand..].starts_with(&needle) {
-                        return Ok(cand);
-                    }
-                }
+        let len = needle.len();
+        let mut matcher = Matcher::new(needle);
+        for (i, score) in Recipies::new(self.0)?.enumerate() {
+            if matcher.push(&score) {
+                return Ok(i + 1 - len);
             }
-            recipies.combine()
         }
+        unreachable!("recipes never run out")
     }
 }
+
+examples! {
+    score_list_5: ScoreList(KITCHEN, 10), "5" => "0124515891";
+    score_list_9: ScoreList(KITCHEN, 10), "9" => "5158916779";
+    score_list_18: ScoreList(KITCHEN, 10), "18" => "9251071085";
+    score_list_2018: ScoreList(KITCHEN, 10), "2018" => "5941429882";
+    first_occurance_5: FirstOccurance(KITCHEN), "01245" => 5;
+    first_occurance_9: FirstOccurance(KITCHEN), "51589" => 9;
+    first_occurance_18: FirstOccurance(KITCHEN), "92510" => 18;
+    first_occurance_2018: FirstOccurance(KITCHEN), "59414" => 2018;
+    // Starts in the middle of the partial match "101" before it
+    first_occurance_overlap: FirstOccurance(KITCHEN), "1012" => 4;
+    three_elves: ScoreList(Kitchen { seeds: &[1, 2, 3], elves: 3 }, 5), "0" => "12367";
+}
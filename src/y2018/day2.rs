@@ -2,6 +2,7 @@ use std::collections::{HashMap, HashSet};
 
 use itertools::Itertools;
 
+use crate::examples;
 use crate::util::{Answer, IterExtra, Lines, Result};
 
 pub struct Checksum(pub [usize; 2]);
@@ -14,8 +15,7 @@ impl Answer for Checksum {
             .map(|id| {
                 id.chars()
                     .frequencies()
-                    .into_iter()
-                    .map(|(_, f)| f)
+                    .into_values()
                     .collect::<HashSet<_>>()
             })
             .fold(HashMap::new(), |mut freq_count, freq_set| {
@@ -58,3 +58,8 @@ impl Answer for ExactDiff {
             .single()
     }
 }
+
+examples! {
+    checksum: Checksum([2, 3]), "abcdef\nbababc\nabbcde\nabcccd\naabcdd\nabcdee\nababab" => 12;
+    exact_diff: ExactDiff(1), "abcde\nfghij\nklmno\npqrst\nfguij\naxcye\nwvxyz" => "fgij";
+}
@@ -1,8 +1,17 @@
-use std::{ops::Range, str::FromStr};
+use std::{
+    collections::{BTreeSet, HashSet},
+    ops::Range,
+    str::FromStr,
+};
 
-use nom::*;
+use itertools::Itertools;
 
-use crate::util::{number, overlap, to_result, Answer, ByLine, Error, IterExtra, Result};
+use crate::examples;
+use crate::util::{
+    overlap,
+    parse::{self, number, preceded, separated_pair, tag, PResult},
+    Answer, ByLine, Error, Result,
+};
 
 #[derive(PartialEq, Eq, Debug)]
 pub struct Claim {
@@ -12,70 +21,149 @@ pub struct Claim {
 }
 
 impl Claim {
-    fn x(&self) -> Range<usize> {
+    const fn x(&self) -> Range<usize> {
         self.corner.0..(self.corner.0 + self.dims.0)
     }
-    fn y(&self) -> Range<usize> {
+    const fn y(&self) -> Range<usize> {
         self.corner.1..(self.corner.1 + self.dims.1)
     }
 }
 
-named!(claim<&str,Claim>, do_parse!(
-    tag!("#")     >>
-    id: number    >>
-    tag!(" @ ")   >>
-    corner: separated_pair!(number, tag!(","), number) >>
-    tag!(": ")    >>
-    dims:  separated_pair!(number, tag!("x"), number) >>
-    (Claim{id, corner, dims})
-));
+fn claim(i: &str) -> PResult<'_, Claim> {
+    let (i, id) = preceded(tag("#"), number)(i)?;
+    let (i, corner) = preceded(tag(" @ "), separated_pair(number, tag(","), number))(i)?;
+    let (i, dims) = preceded(tag(": "), separated_pair(number, tag("x"), number))(i)?;
+    Ok((i, Claim { id, corner, dims }))
+}
 
 impl FromStr for Claim {
     type Err = Error;
     fn from_str(s: &str) -> Result<Self> {
-        to_result(claim(s))
+        parse::all(claim, s)
     }
 }
 
-pub struct TotalOverlapping(pub usize);
-impl Answer for TotalOverlapping {
-    type Input = ByLine<Claim>;
-    type Output = usize;
-    fn ans(&self, inputs: impl Iterator<Item = Claim>) -> Result<Self::Output> {
-        let mut v = vec![0; self.0 * self.0];
+/// Where claims overlap, from a sweep across the fabric.
+pub struct Overlaps {
+    /// Area claimed more than once.
+    pub area: usize,
+    /// Ids of each pair of overlapping claims, lowest first.
+    pub pairs: BTreeSet<(usize, usize)>,
+}
+
+impl Overlaps {
+    pub fn find(claims: &[Claim]) -> Self {
+        // Coordinates are compressed down to the edges of claims, so each row
+        // of the sweep is a run of segments.
+        let ys: Vec<_> = (claims.iter())
+            .flat_map(|c| [c.y().start, c.y().end])
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let segments = |c: &Claim| {
+            let index = |y| ys.binary_search(&y).unwrap();
+            index(c.y().start)..index(c.y().end)
+        };
+
+        // Claims end before others start at the same x.
+        let mut events: Vec<_> = (claims.iter().enumerate())
+            .filter(|(_, c)| !c.x().is_empty() && !c.y().is_empty())
+            .flat_map(|(i, c)| [(c.x().start, true, i), (c.x().end, false, i)])
+            .collect();
+        events.sort_unstable();
 
-        for input in inputs {
-            for x in input.x() {
-                for y in input.y() {
-                    v[x * self.0 + y] += 1;
+        let mut depth = vec![0; ys.len()];
+        let mut active = BTreeSet::<usize>::new();
+        let mut area = 0;
+        let mut pairs = BTreeSet::new();
+        let mut last_x = 0;
+        for (x, start, i) in events {
+            let doubled: usize = (0..ys.len().saturating_sub(1))
+                .filter(|&s| depth[s] > 1)
+                .map(|s| ys[s + 1] - ys[s])
+                .sum();
+            area += doubled * (x - last_x);
+            last_x = x;
+
+            if start {
+                for &j in &active {
+                    if overlap(claims[i].y(), claims[j].y()).is_some() {
+                        let (a, b) = (claims[i].id, claims[j].id);
+                        pairs.insert((a.min(b), a.max(b)));
+                    }
+                }
+                active.insert(i);
+            } else {
+                active.remove(&i);
+            }
+            for s in segments(&claims[i]) {
+                if start {
+                    depth[s] += 1;
+                } else {
+                    depth[s] -= 1;
                 }
             }
         }
-
-        Ok(v.into_iter().filter(|&x| x > 1).count())
+        Self { area, pairs }
     }
 }
 
-fn claims_overlap(c1: &Claim, c2: &Claim) -> bool {
-    overlap(c1.x(), c2.x()).is_some() && overlap(c1.y(), c2.y()).is_some()
+pub struct TotalOverlapping;
+impl Answer for TotalOverlapping {
+    type Input = ByLine<Claim>;
+    type Output = usize;
+    fn ans(&self, inputs: impl Iterator<Item = Claim>) -> Result<Self::Output> {
+        let claims: Vec<_> = inputs.collect();
+        Ok(Overlaps::find(&claims).area)
+    }
 }
 
+/// Ids of every claim that overlaps no other, separated by commas.
 pub struct NonOverlapping;
 impl Answer for NonOverlapping {
     type Input = ByLine<Claim>;
-    type Output = usize;
+    type Output = String;
     fn ans(&self, inputs: impl Iterator<Item = Claim>) -> Result<Self::Output> {
-        let v: Vec<_> = inputs.collect();
-        v.iter()
-            .filter_map(|c1| {
-                if v.iter()
-                    .all(|c2| (c1.id == c2.id) || !claims_overlap(c1, c2))
-                {
-                    Some(c1.id)
-                } else {
-                    None
-                }
-            })
-            .single()
+        let claims: Vec<_> = inputs.collect();
+        let overlaps = Overlaps::find(&claims);
+        let overlapping: HashSet<_> = (overlaps.pairs.iter())
+            .flat_map(|&pair| <[_; 2]>::from(pair))
+            .collect();
+        let ids: Vec<_> = (claims.iter())
+            .map(|c| c.id)
+            .filter(|id| !overlapping.contains(id))
+            .collect();
+        if ids.is_empty() {
+            return Err(Error::Custom("Every claim overlaps another"));
+        }
+        Ok(ids.iter().join(","))
+    }
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2";
+
+#[cfg(test)]
+const NESTED: &str = "#1 @ 2,2: 1x1\n#2 @ 0,0: 2x2\n#3 @ 2,0: 2x2\n#4 @ 1,1: 3x3";
+
+examples! {
+    total_overlapping: TotalOverlapping, EXAMPLE => 4;
+    non_overlapping: NonOverlapping, EXAMPLE => 3;
+    // Claims 2 and 3 only touch, and 1 sits inside 4
+    nested: TotalOverlapping, NESTED => 4;
+    several_non_overlapping: NonOverlapping, "#7 @ 0,0: 2x2\n#8 @ 2,0: 2x2\n#9 @ 1,1: 0x5" => "7,8,9";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overlapping_pairs() {
+        let claims: Vec<Claim> = NESTED.lines().map(|l| l.parse().unwrap()).collect();
+        let overlaps = Overlaps::find(&claims);
+        assert_eq!(overlaps.area, 4);
+        let pairs: Vec<_> = overlaps.pairs.into_iter().collect();
+        assert_eq!(pairs, [(1, 4), (2, 4), (3, 4)]);
     }
 }
@@ -1,43 +1,195 @@
-use std::{collections::HashMap, str::FromStr};
+use std::{collections::HashMap, fmt, ops::Range, str::FromStr};
 
-use nom::*;
+use crate::examples;
+use crate::util::{
+    parse::{self, alt, delimited, fail, map, number, preceded, tag, PResult},
+    Answer, ByLine, Error, Result,
+};
 
-use crate::util::{number, to_result, Answer, ByLine, Error, Result};
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Date {
+    pub year: u32,
+    pub month: u32,
+    pub day: u32,
+}
 
-pub enum Entry {
-    Start(usize),
-    Down(usize),
-    Up(usize),
+impl Date {
+    const fn days_in_month(year: u32, month: u32) -> u32 {
+        let leap =
+            year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400));
+        match month {
+            2 if leap => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+
+    const fn next(self) -> Self {
+        if self.day < Self::days_in_month(self.year, self.month) {
+            Self {
+                day: self.day + 1,
+                ..self
+            }
+        } else if self.month < 12 {
+            Self {
+                month: self.month + 1,
+                day: 1,
+                ..self
+            }
+        } else {
+            Self {
+                year: self.year + 1,
+                month: 1,
+                day: 1,
+            }
+        }
+    }
 }
 
-named!(timestamp<&str,usize>, do_parse!(
-    tag!("[") >> digit >> tag!("-") >> digit >> tag!("-") >> digit >>
-    tag!(" ") >> digit >> tag!(":") >> minute: number >> tag!("] ") >>
-    (minute)
-));
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
 
-named!(start<&str, Entry>, do_parse!(
-    timestamp >> tag!("Guard #") >> n: number >> tag!(" begins shift") >>
-    (Entry::Start(n))
-));
+// Fields are in order of significance, so the derived ordering is
+// chronological.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct DateTime {
+    date: Date,
+    hour: u32,
+    minute: u32,
+}
 
-named!(down<&str, Entry>, do_parse!(
-    minute: timestamp >> tag!("falls asleep") >> (Entry::Down(minute))
-));
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Event {
+    Start(usize),
+    Down,
+    Up,
+}
 
-named!(up<&str, Entry>, do_parse!(
-    minute: timestamp >> tag!("wakes up") >> (Entry::Up(minute))
-));
+pub struct Entry {
+    time: DateTime,
+    event: Event,
+}
 
-named!(entry<&str,Entry>, alt!(start | down | up));
+// "[YYYY-MM-DD hh:mm] "
+fn timestamp(input: &str) -> PResult<'_, DateTime> {
+    let (i, year) = preceded(tag("["), number)(input)?;
+    let (i, month) = preceded(tag("-"), number)(i)?;
+    let (i, day) = preceded(tag("-"), number)(i)?;
+    let (i, hour) = preceded(tag(" "), number)(i)?;
+    let (rest, minute) = delimited(tag(":"), number, tag("] "))(i)?;
+
+    let date = Date { year, month, day };
+    let valid_date =
+        (1..=12).contains(&month) && (1..=Date::days_in_month(year, month)).contains(&day);
+    if !valid_date || hour >= 24 || minute >= 60 {
+        return fail(input, "a valid date and time");
+    }
+    Ok((rest, DateTime { date, hour, minute }))
+}
+
+fn entry(i: &str) -> PResult<'_, Entry> {
+    let (i, time) = timestamp(i)?;
+    let (i, event) = alt(
+        map(
+            delimited(tag("Guard #"), number, tag(" begins shift")),
+            Event::Start,
+        ),
+        alt(
+            map(tag("falls asleep"), |_| Event::Down),
+            map(tag("wakes up"), |_| Event::Up),
+        ),
+    )(i)?;
+    Ok((i, Entry { time, event }))
+}
 
 impl FromStr for Entry {
     type Err = Error;
     fn from_str(s: &str) -> Result<Self> {
-        to_result(entry(s))
+        parse::all(entry, s)
+    }
+}
+
+/// One guard's shift, covering the midnight hour of `date`.
+pub struct Night {
+    pub guard: usize,
+    pub date: Date,
+    /// Minutes of the midnight hour spent asleep, in order.
+    pub naps: Vec<Range<usize>>,
+}
+
+impl Night {
+    /// Total minutes spent asleep.
+    pub fn asleep(&self) -> usize {
+        self.naps.iter().map(ExactSizeIterator::len).sum()
     }
 }
 
+fn still_asleep(night: &Night) -> Error {
+    Error::Message(format!(
+        "Guard #{} is still asleep at the end of the shift on {}",
+        night.guard, night.date
+    ))
+}
+
+/// Splits the log into shifts, in order. Entries can be in any order.
+pub fn nights(inputs: impl Iterator<Item = Entry>) -> Result<Vec<Night>> {
+    let mut entries: Vec<_> = inputs.collect();
+    entries.sort_by_key(|e| e.time);
+
+    let mut nights: Vec<Night> = Vec::new();
+    let mut down = None;
+    for Entry { time, event } in entries {
+        if let Event::Start(guard) = event {
+            if let (Some(_), Some(night)) = (down, nights.last()) {
+                return Err(still_asleep(night));
+            }
+            // Shifts starting in the evening are for the next day.
+            let date = if time.hour >= 12 {
+                time.date.next()
+            } else {
+                time.date
+            };
+            nights.push(Night {
+                guard,
+                date,
+                naps: Vec::new(),
+            });
+            continue;
+        }
+
+        let night = nights
+            .last_mut()
+            .ok_or(Error::Custom("Guard fell asleep or woke before any shift"))?;
+        if time.date != night.date || time.hour != 0 {
+            return Err(Error::Message(format!(
+                "Guard #{} slept outside the midnight hour on {}",
+                night.guard, night.date
+            )));
+        }
+        let minute = time.minute as usize;
+        match (event, down) {
+            (Event::Down, None) => down = Some(minute),
+            (Event::Up, Some(start)) => {
+                night.naps.push(start..minute);
+                down = None;
+            }
+            (Event::Down, Some(_)) => {
+                return Err(Error::Custom("Multiple sleep events without wake"))
+            }
+            (Event::Up, None) => return Err(Error::Custom("Multiple wake event without sleep")),
+            (Event::Start(_), _) => unreachable!(),
+        }
+    }
+    if let (Some(_), Some(night)) = (down, nights.last()) {
+        return Err(still_asleep(night));
+    }
+    Ok(nights)
+}
+
 struct Guard {
     total: usize,
     counts: [usize; 60],
@@ -52,37 +204,17 @@ impl Default for Guard {
     }
 }
 
-fn guards(inputs: impl Iterator<Item = Entry>) -> Result<HashMap<usize, Guard>> {
-    let mut current = 0;
-    let mut down = None;
-
+/// Sleep totals for each guard who fell asleep at least once.
+fn guards(nights: &[Night]) -> HashMap<usize, Guard> {
     let mut guards = HashMap::<usize, Guard>::new();
-
-    for input in inputs {
-        match input {
-            Entry::Start(g) => {
-                current = g;
-                down = None;
-            }
-            Entry::Down(t) => {
-                if down.is_some() {
-                    return Err(Error::Custom("Multiple sleep events without wake"));
-                }
-                down = Some(t);
-            }
-            Entry::Up(end) => {
-                let start = down.ok_or(Error::Custom("Multiple wake event without sleep"))?;
-                down = None;
-                let guard = guards.entry(current).or_default();
-                guard.total += end - start;
-                for minute in start..end {
-                    guard.counts[minute] += 1;
-                }
-            }
+    for night in nights.iter().filter(|n| !n.naps.is_empty()) {
+        let guard = guards.entry(night.guard).or_default();
+        guard.total += night.asleep();
+        for minute in night.naps.iter().flat_map(Range::clone) {
+            guard.counts[minute] += 1;
         }
     }
-
-    Ok(guards)
+    guards
 }
 
 const NO_GUARDS: Error = Error::Custom("No guards fell asleep");
@@ -92,7 +224,7 @@ impl Answer for SleepyMinute {
     type Input = ByLine<Entry>;
     type Output = usize;
     fn ans(&self, inputs: impl Iterator<Item = Entry>) -> Result<Self::Output> {
-        let guards = guards(inputs)?;
+        let guards = guards(&nights(inputs)?);
 
         let (id, guard) = guards
             .into_iter()
@@ -115,7 +247,7 @@ impl Answer for SleepyGuard {
     type Input = ByLine<Entry>;
     type Output = usize;
     fn ans(&self, inputs: impl Iterator<Item = Entry>) -> Result<Self::Output> {
-        let guards = guards(inputs)?;
+        let guards = guards(&nights(inputs)?);
 
         let (id, minute, _) = guards
             .iter()
@@ -126,3 +258,82 @@ impl Answer for SleepyGuard {
         Ok(id * minute)
     }
 }
+
+#[cfg(test)]
+const EXAMPLE: &str = "\
+[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:25] wakes up
+[1518-11-01 00:30] falls asleep
+[1518-11-01 00:55] wakes up
+[1518-11-01 23:58] Guard #99 begins shift
+[1518-11-02 00:40] falls asleep
+[1518-11-02 00:50] wakes up
+[1518-11-03 00:05] Guard #10 begins shift
+[1518-11-03 00:24] falls asleep
+[1518-11-03 00:29] wakes up
+[1518-11-04 00:02] Guard #99 begins shift
+[1518-11-04 00:36] falls asleep
+[1518-11-04 00:46] wakes up
+[1518-11-05 00:03] Guard #99 begins shift
+[1518-11-05 00:45] falls asleep
+[1518-11-05 00:55] wakes up";
+
+examples! {
+    sleepy_minute: SleepyMinute, EXAMPLE => 240;
+    sleepy_guard: SleepyGuard, EXAMPLE => 4455;
+    unsorted: SleepyMinute, &EXAMPLE.lines().rev().collect::<Vec<_>>().join("\n") => 240;
+    evening_start: SleepyMinute, "\
+[1518-12-31 23:45] Guard #7 begins shift
+[1519-01-01 00:10] falls asleep
+[1519-01-01 00:11] wakes up" => 70;
+    midnight_start: SleepyMinute, "\
+[1518-05-06 00:00] Guard #3 begins shift
+[1518-05-06 00:30] falls asleep
+[1518-05-06 00:31] wakes up" => 90;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::Solver;
+
+    #[test]
+    fn longest_nap() {
+        let nights = nights(EXAMPLE.lines().map(|l| l.parse().unwrap())).unwrap();
+        let (night, nap) = (nights.iter())
+            .flat_map(|n| n.naps.iter().map(move |nap| (n, nap)))
+            .max_by_key(|(_, nap)| nap.len())
+            .unwrap();
+        assert_eq!(
+            (night.guard, night.date.to_string()),
+            (10, "1518-11-01".into())
+        );
+        assert_eq!(*nap, 30..55);
+    }
+
+    #[test]
+    fn no_sleep() {
+        let input = "\
+[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-02 00:00] Guard #99 begins shift";
+        for result in &[
+            SleepyMinute.solve(&mut input.as_bytes()),
+            SleepyGuard.solve(&mut input.as_bytes()),
+        ] {
+            match result {
+                Err(e) => assert_eq!(e.to_string(), "No guards fell asleep"),
+                Ok(ans) => panic!("expected an error, got {}", ans),
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_date() {
+        match "[1518-02-30 00:00] falls asleep".parse::<Entry>() {
+            Err(Error::Syntax(e)) => assert_eq!(e.offset, 0),
+            Err(e) => panic!("expected a syntax error, got {:?}", e),
+            Ok(_) => panic!("expected an error for February 30th"),
+        }
+    }
+}
@@ -1,13 +1,14 @@
+use crate::examples;
 use crate::util::{Answer, Bytes, Result};
 
-fn react(b1: u8, b2: u8) -> bool {
+const fn react(b1: u8, b2: u8) -> bool {
     b1.eq_ignore_ascii_case(&b2) && b1 != b2
 }
 
 fn react_len(inputs: impl Iterator<Item = u8>) -> usize {
     let mut to_react = Vec::new();
     for b2 in inputs {
-        if to_react.last().map_or(false, |&b1| react(b1, b2)) {
+        if to_react.last().is_some_and(|&b1| react(b1, b2)) {
             to_react.pop();
         } else {
             to_react.push(b2);
@@ -38,7 +39,7 @@ impl Answer for BestReact {
                 react_len(
                     input
                         .iter()
-                        .cloned()
+                        .copied()
                         .filter(|b| b.to_ascii_lowercase() != c),
                 )
             })
@@ -47,3 +48,8 @@ impl Answer for BestReact {
         Ok(l)
     }
 }
+
+examples! {
+    full_react: FullReact, "dabAcCaCBAcCcaDA" => 10;
+    best_react: BestReact, "dabAcCaCBAcCcaDA" => 4;
+}
@@ -1,93 +1,305 @@
-use std::{cmp::Ordering, str::FromStr};
+use std::{cmp::Ordering, convert::TryFrom, ops::RangeInclusive, str::FromStr};
 
-use nom::*;
+use itertools::Itertools;
 
-use crate::util::{number, to_result, Answer, ByLine, Error, Result};
+use crate::examples;
+use crate::util::{
+    grid::Grid,
+    parse::{self, number, separated_pair, tag},
+    Answer, ByLine, Error, Result,
+};
 
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub struct Point(pub i32, pub i32);
 
-named!(pair<&str, (i32, i32)>, separated_pair!(number, tag!(", "), number));
-
 impl FromStr for Point {
     type Err = Error;
     fn from_str(s: &str) -> Result<Self> {
-        let (x, y) = to_result(pair(s))?;
-        Ok(Point(x, y))
+        let (x, y) = parse::all(separated_pair(number, tag(", "), number), s)?;
+        Ok(Self(x, y))
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum Metric {
+    Manhattan,
+    // The puzzle only uses Manhattan distance; the others are checked by the
+    // examples.
+    #[allow(dead_code)]
+    Chebyshev,
+    #[allow(dead_code)]
+    Euclidean,
+}
+
+impl Metric {
+    // Orders distances from a point exactly, so ties are found without
+    // rounding.
+    fn rank(self, p: Point, q: Point) -> i64 {
+        let dx = i64::from(p.0 - q.0).abs();
+        let dy = i64::from(p.1 - q.1).abs();
+        match self {
+            Self::Manhattan => dx + dy,
+            Self::Chebyshev => dx.max(dy),
+            Self::Euclidean => dx * dx + dy * dy,
+        }
+    }
+
+    fn distance(self, p: Point, q: Point) -> f64 {
+        let dx = f64::from(p.0 - q.0).abs();
+        let dy = f64::from(p.1 - q.1).abs();
+        match self {
+            Self::Manhattan => dx + dy,
+            Self::Chebyshev => dx.max(dy),
+            Self::Euclidean => dx.hypot(dy),
+        }
+    }
+
+    /// Whether the region closest to `points[i]` goes on forever.
+    fn unbounded(self, points: &[Point], i: usize) -> bool {
+        match self {
+            Self::Manhattan => axis_ray(points, i, |p| p),
+            // Chebyshev distance is Manhattan distance rotated by 45 degrees
+            // (and doubled).
+            Self::Chebyshev => axis_ray(points, i, |Point(x, y)| Point(x + y, x - y)),
+            Self::Euclidean => on_hull(points, i),
+        }
+    }
+
+    /// A box containing the region closest to `points[i]`, if it is bounded.
+    fn reach(self, points: &[Point], i: usize) -> Result<Bounds> {
+        match self {
+            // Moving away from the box adds the same to the distance to
+            // every point, so cells outside it are in unbounded regions.
+            Self::Manhattan => bounds(points, 0),
+            // The same holds for the box around the rotated points.
+            Self::Chebyshev => {
+                let rotated: Vec<_> = (points.iter())
+                    .map(|&Point(x, y)| Point(x + y, x - y))
+                    .collect();
+                let (us, vs) = bounds(&rotated, 0)?;
+                let (u0, u1, v0, v1) = (*us.start(), *us.end(), *vs.start(), *vs.end());
+                let half = |n: i32, round_up: bool| (n + i32::from(round_up)).div_euclid(2);
+                Ok((
+                    half(u0 + v0, false)..=half(u1 + v1, true),
+                    half(u0 - v1, false)..=half(u1 - v0, true),
+                ))
+            }
+            Self::Euclidean => voronoi_corners(points, i),
+        }
     }
 }
 
-fn manhattan(p1: &Point, p2: &Point) -> i32 {
-    (p1.0 - p2.0).abs() + (p1.1 - p2.1).abs()
+// With Manhattan distance, the region far out along an axis is split
+// between the points by which has the most to spare going that way. If a
+// point wins anywhere out there, it also wins on the ray along the axis
+// from itself.
+fn axis_ray(points: &[Point], i: usize, transform: impl Fn(Point) -> Point) -> bool {
+    let p = transform(points[i]);
+    let others = || {
+        (points.iter().enumerate())
+            .filter(move |&(j, _)| j != i)
+            .map(|(_, &q)| transform(q))
+    };
+    [(1, 0), (-1, 0), (0, 1), (0, -1)].iter().any(|&(dx, dy)| {
+        others().all(|q| {
+            let (x, y) = (q.0 - p.0, q.1 - p.1);
+            let along = x * dx + y * dy;
+            let across = (x * dy - y * dx).abs();
+            along < across
+        })
+    })
+}
+
+// With Euclidean distance, a point's region is unbounded if the point is on
+// the boundary of the convex hull, which is when all other points are in a
+// closed half-plane around it. Such a half-plane can always be turned until
+// its edge runs through one of the other points.
+fn on_hull(points: &[Point], i: usize) -> bool {
+    let p = points[i];
+    let offsets: Vec<_> = (points.iter().enumerate())
+        .filter(|&(j, _)| j != i)
+        .map(|(_, q)| (i64::from(q.0 - p.0), i64::from(q.1 - p.1)))
+        .collect();
+    // A copy of the point ties with it everywhere.
+    if offsets.contains(&(0, 0)) {
+        return false;
+    }
+    offsets.is_empty()
+        || (offsets.iter()).any(|(wx, wy)| offsets.iter().all(|(vx, vy)| wx * vy - wy * vx >= 0))
+}
+
+// With Euclidean distance, a bounded region is a polygon. Each corner is
+// the center of a circle through the point and two others with no points
+// inside it.
+fn voronoi_corners(points: &[Point], i: usize) -> Result<Bounds> {
+    let offset = |q: Point| {
+        let p = points[i];
+        (i128::from(q.0 - p.0), i128::from(q.1 - p.1))
+    };
+    let others: Vec<_> = (points.iter().enumerate())
+        .filter(|&(j, _)| j != i)
+        .map(|(_, &q)| offset(q))
+        .collect();
+
+    let (mut x_min, mut x_max, mut y_min, mut y_max) = (0, 0, 0, 0);
+    for (a, &(bx, by)) in others.iter().enumerate() {
+        for &(cx, cy) in &others[a + 1..] {
+            // The center is (ux / d, uy / d) from the point.
+            let mut d = 2 * (bx * cy - by * cx);
+            if d == 0 {
+                continue;
+            }
+            let (b2, c2) = (bx * bx + by * by, cx * cx + cy * cy);
+            let (mut ux, mut uy) = (cy * b2 - by * c2, bx * c2 - cx * b2);
+            if d < 0 {
+                d = -d;
+                ux = -ux;
+                uy = -uy;
+            }
+            let radius = ux * ux + uy * uy;
+            let empty = (others.iter())
+                .all(|&(mx, my)| (ux - mx * d).pow(2) + (uy - my * d).pow(2) >= radius);
+            if empty {
+                x_min = x_min.min(ux.div_euclid(d));
+                x_max = x_max.max(-(-ux).div_euclid(d));
+                y_min = y_min.min(uy.div_euclid(d));
+                y_max = y_max.max(-(-uy).div_euclid(d));
+            }
+        }
+    }
+
+    let Point(x, y) = points[i];
+    let coord = |n: i128, base: i32| {
+        i32::try_from(n)
+            .ok()
+            .and_then(|n| n.checked_add(base))
+            .ok_or(Error::Custom("Region is too large"))
+    };
+    Ok((
+        coord(x_min, x)?..=coord(x_max, x)?,
+        coord(y_min, y)?..=coord(y_max, y)?,
+    ))
+}
+
+// Index of the point strictly closest to the cell, if there is one.
+fn closest(points: &[Point], metric: Metric, cell: Point) -> Option<usize> {
+    let mut best = None;
+    let mut best_rank = i64::MAX;
+    for (i, &p) in points.iter().enumerate() {
+        let rank = metric.rank(cell, p);
+        match rank.cmp(&best_rank) {
+            Ordering::Less => {
+                best = Some(i);
+                best_rank = rank;
+            }
+            Ordering::Equal => best = None,
+            Ordering::Greater => {}
+        }
+    }
+    best
 }
 
 const NO_POINTS: Error = Error::Custom("No points in input");
+const ALL_INFINITE: Error = Error::Custom("Every region is infinite");
+
+// A box of cells, as ranges of x and y.
+type Bounds = (RangeInclusive<i32>, RangeInclusive<i32>);
 
-pub struct LargestFinite(pub i32, pub i32);
+// The smallest box containing all the points, padded on every side.
+fn bounds(points: &[Point], pad: i32) -> Result<Bounds> {
+    let (x_min, x_max) = points
+        .iter()
+        .map(|p| p.0)
+        .minmax()
+        .into_option()
+        .ok_or(NO_POINTS)?;
+    let (y_min, y_max) = points
+        .iter()
+        .map(|p| p.1)
+        .minmax()
+        .into_option()
+        .ok_or(NO_POINTS)?;
+    Ok((x_min - pad..=x_max + pad, y_min - pad..=y_max + pad))
+}
+
+// The smallest range covering both.
+fn join(a: RangeInclusive<i32>, b: RangeInclusive<i32>) -> RangeInclusive<i32> {
+    *a.start().min(b.start())..=*a.end().max(b.end())
+}
+
+pub struct LargestFinite(pub Metric);
 impl Answer for LargestFinite {
     type Input = ByLine<Point>;
     type Output = usize;
     fn ans(&self, inputs: impl Iterator<Item = Point>) -> Result<Self::Output> {
         let points = inputs.collect::<Vec<_>>();
-        let mut counts = vec![Some(0); points.len()];
-
-        for x in self.0..=self.1 {
-            for y in self.0..=self.1 {
-                // Find the closest point
-                let p = Point(x, y);
-                let mut idx = 0;
-                let mut best_distance = i32::max_value();
-                let mut num_ties = 0;
-
-                for (i, q) in points.iter().enumerate() {
-                    let d = manhattan(&p, q);
-                    match d.cmp(&best_distance) {
-                        Ordering::Less => {
-                            num_ties = 1;
-                            best_distance = d;
-                            idx = i;
-                        }
-                        Ordering::Equal => {
-                            num_ties += 1;
-                        }
-                        Ordering::Greater => {}
-                    };
-                }
-                if num_ties == 1 {
-                    if let Some(x) = counts[idx].as_mut() {
-                        *x += 1;
-                    }
-                }
-
-                // Kill any on the edges
-                if x == self.0 || y == self.0 || x == self.1 || y == self.1 {
-                    counts[idx] = None
-                }
-            }
+        let finite: Vec<_> = (0..points.len())
+            .map(|i| !self.0.unbounded(&points, i))
+            .collect();
+        // Only the cells within reach of a bounded region matter.
+        let mut reach = None;
+        for i in (0..points.len()).filter(|&i| finite[i]) {
+            let (xs, ys) = self.0.reach(&points, i)?;
+            reach = Some(match reach {
+                None => (xs, ys),
+                Some((x, y)) => (join(x, xs), join(y, ys)),
+            });
+        }
+        let (xs, ys) = reach.ok_or(ALL_INFINITE)?;
+
+        let (width, height) = (xs.clone().count(), ys.clone().count());
+        let owners = Grid::from_fn(width, height, |p| {
+            closest(&points, self.0, Point(xs.start() + p.x, ys.start() + p.y))
+        });
+        let mut counts = vec![0; points.len()];
+        for &i in owners.values().filter_map(Option::as_ref) {
+            counts[i] += 1;
         }
 
-        counts.into_iter().filter_map(|x| x).max().ok_or(NO_POINTS)
+        (counts.into_iter())
+            .zip(finite)
+            .filter(|&(_, finite)| finite)
+            .map(|(count, _)| count)
+            .max()
+            .ok_or(ALL_INFINITE)
     }
 }
 
-pub struct Close(pub i32, pub i32, pub i32);
+pub struct Close(pub Metric, pub i32);
 impl Answer for Close {
     type Input = ByLine<Point>;
     type Output = usize;
     fn ans(&self, inputs: impl Iterator<Item = Point>) -> Result<Self::Output> {
         let points = inputs.collect::<Vec<_>>();
-        let mut num_points = 0;
-
-        for x in self.0..=self.1 {
-            for y in self.0..=self.1 {
-                // Sum manhattan distance
-                let p = Point(x, y);
-                let s: i32 = points.iter().map(|q| manhattan(&p, q)).sum();
-                if s < self.2 {
-                    num_points += 1;
-                }
-            }
-        }
+        // Under any of the metrics, each step away from the box adds at
+        // least one to the distance to every point.
+        let pad = self.1 / points.len().max(1) as i32 + 1;
+        let (xs, ys) = bounds(&points, pad)?;
 
-        Ok(num_points)
+        let threshold = f64::from(self.1);
+        let close = |x, y| {
+            let cell = Point(x, y);
+            let total: f64 = points.iter().map(|&p| self.0.distance(cell, p)).sum();
+            total < threshold
+        };
+        Ok(xs
+            .flat_map(|x| ys.clone().map(move |y| (x, y)))
+            .filter(|&(x, y)| close(x, y))
+            .count())
     }
 }
+
+#[cfg(test)]
+const EXAMPLE: &str = "1, 1\n1, 6\n8, 3\n3, 4\n5, 5\n8, 9";
+
+// Answers for the other metrics were checked by brute force over a wide box.
+examples! {
+    largest_finite: LargestFinite(Metric::Manhattan), EXAMPLE => 17;
+    largest_finite_chebyshev: LargestFinite(Metric::Chebyshev), EXAMPLE => 10;
+    largest_finite_euclidean: LargestFinite(Metric::Euclidean), EXAMPLE => 16;
+    // Diagonal neighbours of the middle point tie with the corners.
+    largest_finite_ties: LargestFinite(Metric::Euclidean), "0, 0\n4, 0\n0, 4\n4, 4\n2, 2" => 5;
+    close: Close(Metric::Manhattan, 32), EXAMPLE => 16;
+    close_chebyshev: Close(Metric::Chebyshev, 20), EXAMPLE => 10;
+    close_euclidean: Close(Metric::Euclidean, 25), EXAMPLE => 20;
+}
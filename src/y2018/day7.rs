@@ -1,93 +1,40 @@
-use std::{
-    cmp::Reverse,
-    collections::{hash_map::Entry, BinaryHeap, HashMap},
-    hash::Hash,
-    str::FromStr,
-};
-
-use nom::*;
+use std::str::FromStr;
 
-use crate::util::{to_result, Answer, ByLine, Error, Result};
+use crate::examples;
+use crate::util::{
+    parse::{self, alphanumeric, delimited, preceded, tag, PResult},
+    schedule, Answer, ByLine, Error, Result,
+};
 
 pub struct Dependancy<J> {
     first: J,
     next: J,
 }
 
-named!(dependancy<&str,Dependancy<String>>, do_parse!(
-    tag!("Step ") >> first: map!(alphanumeric, String::from) >>
-    tag!(" must be finished before step ") >>
-    next: map!(alphanumeric, String::from) >> tag!(" can begin.") >>
-    (Dependancy{first, next})
-));
+fn dependancy(i: &str) -> PResult<'_, Dependancy<String>> {
+    let (i, first) = preceded(tag("Step "), alphanumeric)(i)?;
+    let (i, next) = delimited(
+        tag(" must be finished before step "),
+        alphanumeric,
+        tag(" can begin."),
+    )(i)?;
+    let dep = Dependancy {
+        first: first.to_string(),
+        next: next.to_string(),
+    };
+    Ok((i, dep))
+}
 
 impl FromStr for Dependancy<String> {
     type Err = Error;
     fn from_str(s: &str) -> Result<Self> {
-        to_result(dependancy(s))
+        parse::all(dependancy, s)
     }
 }
 
-struct Scheduler<J: Eq + Ord + Hash> {
-    depends_on: HashMap<J, Vec<J>>,
-    remaining_deps: HashMap<J, usize>,
-    ready: BinaryHeap<Reverse<J>>,
-}
-
-impl<J: Eq + Ord + Hash> Scheduler<J> {
-    // Inputs should be deduplicated before being passed
-    fn new(inputs: impl Iterator<Item = Dependancy<J>>) -> Self
-    where
-        J: Clone,
-    {
-        let mut depends_on = HashMap::<J, Vec<J>>::new();
-        let mut remaining_deps = HashMap::<J, usize>::new();
-        for Dependancy { first, next } in inputs {
-            let nexts = depends_on.entry(first).or_default();
-            nexts.push(next);
-            let next = nexts.last().unwrap();
-            if let Some(count) = remaining_deps.get_mut(next) {
-                *count += 1;
-            } else {
-                remaining_deps.insert(next.clone(), 1);
-            }
-        }
-
-        let ready = depends_on
-            .keys()
-            .filter_map(|j| {
-                if remaining_deps.contains_key(j) {
-                    None
-                } else {
-                    Some(Reverse(j.clone()))
-                }
-            })
-            .collect();
-
-        Self {
-            depends_on,
-            remaining_deps,
-            ready,
-        }
-    }
-    fn complete(&mut self, j: &J) {
-        if let Some(jobs) = self.depends_on.remove(j) {
-            for job in jobs {
-                let mut e = match self.remaining_deps.entry(job) {
-                    Entry::Occupied(e) => e,
-                    _ => unreachable!(),
-                };
-                let count = e.get_mut();
-                *count -= 1;
-                if *count == 0 {
-                    let (job, _) = e.remove_entry();
-                    self.ready.push(Reverse(job));
-                }
-            }
-        }
-    }
-    fn next_job(&mut self) -> Option<J> {
-        Some(self.ready.pop()?.0)
+impl<J> Dependancy<J> {
+    fn edge(self) -> (J, J) {
+        (self.first, self.next)
     }
 }
 
@@ -96,59 +43,52 @@ impl Answer for Order {
     type Input = ByLine<Dependancy<String>>;
     type Output = String;
     fn ans(&self, inputs: impl Iterator<Item = Dependancy<String>>) -> Result<Self::Output> {
-        let mut s = Scheduler::new(inputs);
-        let mut answer = String::new();
-
-        while let Some(job) = s.next_job() {
-            answer.push_str(&job);
-            s.complete(&job);
-        }
-        Ok(answer)
+        Ok(schedule::order(inputs.map(Dependancy::edge))?.concat())
     }
 }
 
-fn cost(s: &str) -> usize {
-    (s.chars().next().unwrap() as usize - 'A' as usize) + 61
+// "A" -> 1, "B" -> 2, ...
+fn letter(s: &str) -> Option<usize> {
+    match s.as_bytes() {
+        [c @ b'A'..=b'Z'] => Some((c - b'A') as usize + 1),
+        _ => None,
+    }
 }
 
-pub struct Workers(pub usize);
+/// Number of workers, and the time every step takes on top of its letter's
+/// position in the alphabet.
+pub struct Workers(pub usize, pub usize);
 impl Answer for Workers {
     type Input = ByLine<Dependancy<String>>;
     type Output = usize;
     fn ans(&self, inputs: impl Iterator<Item = Dependancy<String>>) -> Result<Self::Output> {
-        let mut s = Scheduler::new(inputs);
-        let mut counter = 0;
-        let mut running = Vec::new();
-
-        loop {
-            // Cleanup finished jobs
-            running.retain(|(name, count)| {
-                if *count == 0 {
-                    s.complete(name);
-                    false
-                } else {
-                    true
-                }
-            });
-
-            // Assign new jobs
-            while running.len() < self.0 {
-                if let Some(job) = s.next_job() {
-                    let c = cost(&job);
-                    running.push((job, c));
-                } else {
-                    if running.is_empty() {
-                        return Ok(counter);
-                    }
-                    break;
-                }
-            }
-
-            // Advance counters
-            for (_, count) in &mut running {
-                *count -= 1;
-            }
-            counter += 1;
+        let edges: Vec<_> = inputs.map(Dependancy::edge).collect();
+        let invalid = (edges.iter())
+            .flat_map(|(first, next)| [first, next])
+            .find(|s| letter(s).is_none());
+        if let Some(step) = invalid {
+            return Err(Error::Message(format!(
+                "Step {step:?} is not a capital letter"
+            )));
         }
+
+        let cost = |s: &String| letter(s).unwrap() + self.1;
+        let timeline = schedule::simulate(edges, self.0, cost)?;
+        Ok(timeline.total)
     }
 }
+
+#[cfg(test)]
+const EXAMPLE: &str = "\
+Step C must be finished before step A can begin.
+Step C must be finished before step F can begin.
+Step A must be finished before step B can begin.
+Step A must be finished before step D can begin.
+Step B must be finished before step E can begin.
+Step D must be finished before step E can begin.
+Step F must be finished before step E can begin.";
+
+examples! {
+    order: Order, EXAMPLE => "CABDFE";
+    workers: Workers(2, 0), EXAMPLE => 15;
+}
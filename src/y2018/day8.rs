@@ -1,8 +1,9 @@
+use crate::examples;
 use crate::util::{Answer, ByWhitespace, Error, Result};
 
 #[derive(Default)]
 struct Node {
-    nodes: Vec<Node>,
+    nodes: Vec<Self>,
     metadata: Vec<usize>,
 }
 
@@ -30,17 +31,17 @@ impl Node {
     }
 
     fn metadata_sum(&self) -> usize {
-        let s1: usize = self.metadata.iter().cloned().sum();
-        let s2: usize = self.nodes.iter().map(|n| n.metadata_sum()).sum();
+        let s1: usize = self.metadata.iter().copied().sum();
+        let s2: usize = self.nodes.iter().map(Self::metadata_sum).sum();
         s1 + s2
     }
     fn value(&self) -> usize {
         if self.nodes.is_empty() {
-            return self.metadata.iter().cloned().sum();
+            return self.metadata.iter().copied().sum();
         }
         self.metadata
             .iter()
-            .flat_map(|&idx| Some(self.nodes.get(idx - 1)?.value()))
+            .filter_map(|&idx| Some(self.nodes.get(idx - 1)?.value()))
             .sum()
     }
 }
@@ -62,3 +63,8 @@ impl Answer for Value {
         Ok(Node::make(inputs)?.value())
     }
 }
+
+examples! {
+    sum: Sum, "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2" => 138;
+    value: Value, "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2" => 66;
+}
@@ -1,90 +1,100 @@
-use std::collections::HashMap;
-
-use intrusive_collections::{
-    intrusive_adapter,
-    linked_list::{CursorMut, Link, LinkedList},
-    Adapter,
-};
+use std::collections::VecDeque;
 
+use crate::examples;
 use crate::util::{Answer, ByWhitespace, Error, Result};
 
-struct Node {
-    link: Link,
-    value: usize,
+/// Every `divisor`th marble is kept by the player instead of placed, along
+/// with the marble `back` places counter-clockwise of the current one.
+#[derive(Copy, Clone, Debug)]
+pub struct Rules {
+    pub divisor: usize,
+    pub back: usize,
 }
 
-impl Node {
-    fn new(value: usize) -> Box<Self> {
-        Box::new(Self {
-            link: Link::new(),
-            value,
-        })
-    }
-}
+pub const RULES: Rules = Rules {
+    divisor: 23,
+    back: 7,
+};
 
-#[allow(clippy::expl_impl_clone_on_copy)]
-intrusive_adapter!(MyAdapter = Box<Node>: Node { link: Link });
+// The circle is kept rotated so the current marble is at the back, with
+// clockwise running from the front.
+struct Circle(VecDeque<usize>);
 
-fn shift<A: Adapter<Link = Link>>(cursor: &mut CursorMut<A>, offset: isize) {
-    let forward = offset >= 0;
-    let mut count = 0;
-    while count < offset.abs() {
-        if forward {
-            cursor.move_next();
-        } else {
-            cursor.move_prev();
-        }
-        if !cursor.is_null() {
-            count += 1;
+impl Circle {
+    fn clockwise(&mut self, n: usize) {
+        if !self.0.is_empty() {
+            let n = n % self.0.len();
+            self.0.rotate_left(n);
         }
     }
-}
 
-struct Game {
-    cursor: CursorMut<'static, MyAdapter>,
-    scores: HashMap<u32, usize>,
+    fn counter_clockwise(&mut self, n: usize) {
+        if !self.0.is_empty() {
+            let n = n % self.0.len();
+            self.0.rotate_right(n);
+        }
+    }
 }
 
-impl Game {
-    fn new() -> Self {
-        let marbles = Box::leak(Box::new(LinkedList::new(MyAdapter::new())));
-        marbles.push_back(Node::new(0));
-        Self {
-            cursor: marbles.cursor_mut(),
-            scores: HashMap::new(),
-        }
+fn high_score(rules: Rules, players: usize, marbles: usize) -> Result<usize> {
+    if players == 0 {
+        return Err(Error::Custom("No players"));
+    }
+    if rules.divisor == 0 {
+        return Err(Error::Custom("Divisor must be positive"));
     }
 
-    fn add(&mut self, player: u32, marble: usize) {
-        if marble % 23 == 0 {
-            shift(&mut self.cursor, -7);
-            let score = self.cursor.remove().unwrap().value + marble;
-            *self.scores.entry(player).or_default() += score;
+    let mut circle = Circle(VecDeque::with_capacity(marbles + 1));
+    circle.0.push_back(0);
+    let mut scores = vec![0; players];
+    for marble in 1..=marbles {
+        if marble % rules.divisor == 0 {
+            circle.counter_clockwise(rules.back);
+            let kept = circle.0.pop_back().unwrap_or_default();
+            circle.clockwise(1);
+            scores[marble % players] += marble + kept;
         } else {
-            shift(&mut self.cursor, 1);
-            self.cursor.insert_after(Node::new(marble));
-            shift(&mut self.cursor, 1);
+            circle.clockwise(1);
+            circle.0.push_back(marble);
         }
     }
+    Ok(scores.into_iter().max().unwrap_or_default())
+}
+
+fn read_game(mut input: impl Iterator<Item = usize>) -> Result<(usize, usize)> {
+    let players = input.next().ok_or(Error::Custom("No Num Player"))?;
+    let marbles = input.next().ok_or(Error::Custom("No Num Marbles"))?;
+    Ok((players, marbles))
 }
 
 // Input is "<players> <marbles>"
-pub struct WinningScore;
+pub struct WinningScore(pub Rules);
 impl Answer for WinningScore {
     type Input = ByWhitespace<usize>;
     type Output = usize;
-    fn ans(&self, mut input: impl Iterator<Item = usize>) -> Result<Self::Output> {
-        let players = input.next().ok_or(Error::Custom("No Num Player"))?;
-        let marbles = input.next().ok_or(Error::Custom("No Num Marbles"))?;
-        let mut g = Game::new();
-        for marble in 1..=marbles {
-            let player = ((marble % players) + 1) as u32;
-            g.add(player, marble);
-        }
-        g.scores
-            .values()
-            .cloned()
-            .max()
-            .ok_or(Error::Custom("No Marbles"))
+    fn ans(&self, input: impl Iterator<Item = usize>) -> Result<Self::Output> {
+        let (players, marbles) = read_game(input)?;
+        high_score(self.0, players, marbles)
     }
 }
+
+/// The winning score with `.1` times as many marbles.
+pub struct LongerGame(pub Rules, pub usize);
+impl Answer for LongerGame {
+    type Input = ByWhitespace<usize>;
+    type Output = usize;
+    fn ans(&self, input: impl Iterator<Item = usize>) -> Result<Self::Output> {
+        let (players, marbles) = read_game(input)?;
+        high_score(self.0, players, marbles * self.1)
+    }
+}
+
+examples! {
+    nine_players: WinningScore(RULES), "9 25" => 32;
+    ten_players: WinningScore(RULES), "10 1618" => 8317;
+    thirteen_players: WinningScore(RULES), "13 7999" => 146_373;
+    seventeen_players: WinningScore(RULES), "17 1104" => 2764;
+    twenty_one_players: WinningScore(RULES), "21 6111" => 54718;
+    thirty_players: WinningScore(RULES), "30 5807" => 37305;
+    longer_game: LongerGame(RULES, 100), "9 25" => 22563;
+}
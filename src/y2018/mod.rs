@@ -13,3 +13,38 @@ pub mod day11;
 pub mod day12;
 pub mod day13;
 pub mod day14;
+
+use crate::util::Registry;
+
+const YEAR: i32 = 2018;
+
+pub fn register(r: &mut Registry) {
+    r.add(YEAR, 1, 1, day1::Sum);
+    r.add(YEAR, 1, 2, day1::FirstRepeat);
+    r.add(YEAR, 2, 1, day2::Checksum([2, 3]));
+    r.add(YEAR, 2, 2, day2::ExactDiff(1));
+    r.add(YEAR, 3, 1, day3::TotalOverlapping);
+    r.add(YEAR, 3, 2, day3::NonOverlapping);
+    r.add(YEAR, 4, 1, day4::SleepyMinute);
+    r.add(YEAR, 4, 2, day4::SleepyGuard);
+    r.add(YEAR, 5, 1, day5::FullReact);
+    r.add(YEAR, 5, 2, day5::BestReact);
+    r.add(YEAR, 6, 1, day6::LargestFinite(day6::Metric::Manhattan));
+    r.add(YEAR, 6, 2, day6::Close(day6::Metric::Manhattan, 10_000));
+    r.add(YEAR, 7, 1, day7::Order);
+    r.add(YEAR, 7, 2, day7::Workers(5, 60));
+    r.add(YEAR, 8, 1, day8::Sum);
+    r.add(YEAR, 8, 2, day8::Value);
+    r.add(YEAR, 9, 1, day9::WinningScore(day9::RULES));
+    r.add(YEAR, 9, 2, day9::LongerGame(day9::RULES, 100));
+    r.add(YEAR, 10, 1, day10::Message);
+    r.add(YEAR, 10, 2, day10::Seconds);
+    r.add(YEAR, 11, 1, day11::LargestPower(300, 3));
+    r.add(YEAR, 11, 2, day11::Overall(300));
+    r.add(YEAR, 12, 1, day12::PlantSum(20));
+    r.add(YEAR, 12, 2, day12::PlantSum(50_000_000_000));
+    r.add(YEAR, 13, 1, day13::FirstCrash);
+    r.add(YEAR, 13, 2, day13::LastCart);
+    r.add(YEAR, 14, 1, day14::ScoreList(day14::KITCHEN, 10));
+    r.add(YEAR, 14, 2, day14::FirstOccurance(day14::KITCHEN));
+}